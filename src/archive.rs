@@ -0,0 +1,318 @@
+use std::any::Any;
+use std::io::{self, Read, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::ptr;
+use std::slice;
+
+use ffi;
+use gio;
+use glib::{Error, FileError};
+use glib::translate::*;
+use glib_ffi::gpointer;
+use libc::{c_char, c_int, c_uint, c_void, size_t, ssize_t};
+
+use io_utils::to_glib_error;
+use libarchive::*;
+use MutableTree;
+use Repo;
+use RepoCommitModifier;
+use RepoFile;
+
+const READ_BLOCK_SIZE: usize = 64 * 1024;
+
+/// Options for `Repo::import_archive_to_mtree()`.
+#[derive(Clone, Debug, Default)]
+pub struct ImportArchiveOptions {
+    /// Skip entries that can't be represented, like device nodes, instead
+    /// of failing.
+    pub ignore_unsupported_content: bool,
+    /// Create missing parent directories of entries.
+    pub autocreate_parents: bool,
+    /// Move `/etc` to `/usr/etc` and skip `/var` content, as expected for
+    /// deployable trees.
+    pub use_ostree_convention: bool,
+}
+
+/// Options for `Repo::export_tree_to_archive()`.
+#[derive(Clone, Debug, Default)]
+pub struct ExportArchiveOptions {
+    /// Don't store extended attributes.
+    pub disable_xattrs: bool,
+    /// The modification time of all entries; 0 keeps the default.
+    pub timestamp_secs: u64,
+}
+
+// Full layouts of OstreeRepoImportArchiveOptions and
+// OstreeRepoExportArchiveOptions, whose bitfields ostree-sys can't express.
+
+#[repr(C)]
+struct RawImportArchiveOptions {
+    flags: c_uint,
+    unused_uint: [c_uint; 8],
+    unused_ptrs: [gpointer; 8],
+}
+
+#[repr(C)]
+struct RawExportArchiveOptions {
+    flags: c_uint,
+    timestamp_secs: u64,
+    unused_uint: [c_uint; 8],
+    path_prefix: *mut c_char,
+    unused_ptrs: [gpointer; 7],
+}
+
+/// Returns the mask of the `n`th one-bit field in a bitfield word.
+fn bitfield(n: u32, set: bool) -> c_uint {
+    if !set {
+        0
+    } else if cfg!(target_endian = "big") {
+        1 << (31 - n)
+    } else {
+        1 << n
+    }
+}
+
+impl<'a> From<&'a ImportArchiveOptions> for RawImportArchiveOptions {
+    fn from(options: &'a ImportArchiveOptions) -> RawImportArchiveOptions {
+        RawImportArchiveOptions {
+            flags: bitfield(0, options.ignore_unsupported_content)
+                | bitfield(1, options.autocreate_parents)
+                | bitfield(2, options.use_ostree_convention),
+            unused_uint: [0; 8],
+            unused_ptrs: [ptr::null_mut(); 8],
+        }
+    }
+}
+
+impl<'a> From<&'a ExportArchiveOptions> for RawExportArchiveOptions {
+    fn from(options: &'a ExportArchiveOptions) -> RawExportArchiveOptions {
+        RawExportArchiveOptions {
+            flags: bitfield(0, options.disable_xattrs),
+            timestamp_secs: options.timestamp_secs,
+            unused_uint: [0; 8],
+            path_prefix: ptr::null_mut(),
+            unused_ptrs: [ptr::null_mut(); 7],
+        }
+    }
+}
+
+/// Owns a libarchive handle and the state of its stream callbacks.
+struct Archive<T> {
+    raw: *mut archive,
+    read: bool,
+    // Boxed so the address handed to libarchive stays put.
+    client: Box<Client<T>>,
+}
+
+struct Client<T> {
+    inner: T,
+    buffer: Vec<u8>,
+    error: Option<io::Error>,
+    // A panic of the reader or writer, resumed once libarchive returns.
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+impl<T> Archive<T> {
+    fn new_read(inner: T) -> Archive<T> {
+        unsafe {
+            let raw = archive_read_new();
+            archive_read_support_filter_all(raw);
+            archive_read_support_format_all(raw);
+            Archive::new(raw, true, inner)
+        }
+    }
+
+    fn new_write(inner: T) -> Archive<T> {
+        unsafe {
+            let raw = archive_write_new();
+            archive_write_set_format_pax_restricted(raw);
+            Archive::new(raw, false, inner)
+        }
+    }
+
+    fn new(raw: *mut archive, read: bool, inner: T) -> Archive<T> {
+        Archive {
+            raw,
+            read,
+            client: Box::new(Client {
+                inner,
+                buffer: Vec::new(),
+                error: None,
+                panic: None,
+            }),
+        }
+    }
+
+    fn client_data(&mut self) -> *mut c_void {
+        &mut *self.client as *mut Client<T> as *mut c_void
+    }
+
+    /// Checks the return value of a libarchive call.
+    fn check(&mut self, ret: c_int) -> Result<(), Error> {
+        self.resume_panic();
+        if ret == ARCHIVE_OK { Ok(()) } else { Err(self.error()) }
+    }
+
+    /// Continues unwinding a panic caught in a callback.
+    fn resume_panic(&mut self) {
+        if let Some(payload) = self.client.panic.take() {
+            panic::resume_unwind(payload);
+        }
+    }
+
+    /// Returns the error of the last failed operation, preferring errors of
+    /// the underlying reader or writer.
+    fn error(&mut self) -> Error {
+        if let Some(err) = self.client.error.take() {
+            return to_glib_error(&err);
+        }
+        let message: Option<String> = unsafe { from_glib_none(archive_error_string(self.raw)) };
+        Error::new(FileError::Failed, &message.unwrap_or_else(|| "libarchive error".to_owned()))
+    }
+}
+
+impl<T> Drop for Archive<T> {
+    fn drop(&mut self) {
+        unsafe {
+            if self.read {
+                archive_read_free(self.raw);
+            } else {
+                archive_write_free(self.raw);
+            }
+        }
+    }
+}
+
+/// Runs the body of a callback on the client behind `client_data`. Panics
+/// must not unwind into libarchive, so they are stashed in the client and
+/// `fatal` is returned instead.
+unsafe fn with_client<T, R, F: FnOnce(&mut Client<T>) -> R>(client_data: *mut c_void, fatal: R, f: F) -> R {
+    let client = &mut *(client_data as *mut Client<T>);
+    if client.panic.is_some() {
+        return fatal;
+    }
+    match panic::catch_unwind(AssertUnwindSafe(|| f(client))) {
+        Ok(ret) => ret,
+        Err(payload) => {
+            client.panic = Some(payload);
+            fatal
+        }
+    }
+}
+
+unsafe extern "C" fn read_callback<R: Read>(_: *mut archive, client_data: *mut c_void, buffer: *mut *const c_void) -> ssize_t {
+    with_client(client_data, ARCHIVE_FATAL as ssize_t, |client: &mut Client<R>| {
+        client.buffer.resize(READ_BLOCK_SIZE, 0);
+        loop {
+            match client.inner.read(&mut client.buffer) {
+                Ok(n) => {
+                    *buffer = client.buffer.as_ptr() as *const c_void;
+                    return n as ssize_t;
+                }
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => {
+                    client.error = Some(err);
+                    return -1;
+                }
+            }
+        }
+    })
+}
+
+unsafe extern "C" fn write_callback<W: Write>(_: *mut archive, client_data: *mut c_void, buffer: *const c_void, length: size_t) -> ssize_t {
+    with_client(client_data, ARCHIVE_FATAL as ssize_t, |client: &mut Client<W>| {
+        let data = slice::from_raw_parts(buffer as *const u8, length);
+        match client.inner.write_all(data) {
+            Ok(()) => length as ssize_t,
+            Err(err) => {
+                client.error = Some(err);
+                -1
+            }
+        }
+    })
+}
+
+unsafe extern "C" fn close_callback<W: Write>(_: *mut archive, client_data: *mut c_void) -> c_int {
+    with_client(client_data, ARCHIVE_FATAL, |client: &mut Client<W>| {
+        match client.inner.flush() {
+            Ok(()) => ARCHIVE_OK,
+            Err(err) => {
+                client.error = Some(err);
+                ARCHIVE_FATAL
+            }
+        }
+    })
+}
+
+impl Repo {
+    /// Imports the tar archive at `path`, optionally compressed, into
+    /// `mtree`.
+    pub fn import_archive_to_mtree<P: AsRef<Path>>(&self, options: &ImportArchiveOptions, path: P, mtree: &MutableTree, modifier: Option<&RepoCommitModifier>, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        let mut archive = Archive::new_read(());
+        let ret = unsafe {
+            archive_read_open_filename(archive.raw, path.as_ref().to_glib_none().0, READ_BLOCK_SIZE)
+        };
+        archive.check(ret)?;
+        self.import_archive(options, &mut archive, mtree, modifier, cancellable)
+    }
+
+    /// Like `import_archive_to_mtree()`, reading the archive from `reader`.
+    pub fn import_archive_to_mtree_from_read<R: Read>(&self, options: &ImportArchiveOptions, reader: R, mtree: &MutableTree, modifier: Option<&RepoCommitModifier>, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        let mut archive = Archive::new_read(reader);
+        let ret = unsafe {
+            archive_read_open(archive.raw, archive.client_data(), None, Some(read_callback::<R>), None)
+        };
+        archive.check(ret)?;
+        self.import_archive(options, &mut archive, mtree, modifier, cancellable)
+    }
+
+    /// Writes the tree `root` as a tar archive to `path`.
+    pub fn export_tree_to_archive<P: AsRef<Path>>(&self, options: &ExportArchiveOptions, root: &RepoFile, path: P, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        let mut archive = Archive::new_write(());
+        let ret = unsafe {
+            archive_write_open_filename(archive.raw, path.as_ref().to_glib_none().0)
+        };
+        archive.check(ret)?;
+        self.export_tree(options, root, &mut archive, cancellable)
+    }
+
+    /// Like `export_tree_to_archive()`, writing the archive to `writer`.
+    pub fn export_tree_to_writer<W: Write>(&self, options: &ExportArchiveOptions, root: &RepoFile, writer: W, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        let mut archive = Archive::new_write(writer);
+        let ret = unsafe {
+            archive_write_open(archive.raw, archive.client_data(), None, Some(write_callback::<W>), Some(close_callback::<W>))
+        };
+        archive.check(ret)?;
+        self.export_tree(options, root, &mut archive, cancellable)
+    }
+
+    fn import_archive<T>(&self, options: &ImportArchiveOptions, archive: &mut Archive<T>, mtree: &MutableTree, modifier: Option<&RepoCommitModifier>, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        let mut opts = RawImportArchiveOptions::from(options);
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_import_archive_to_mtree(self.to_glib_none().0, &mut opts as *mut _ as *mut _, archive.raw as *mut c_void, mtree.to_glib_none().0, modifier.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            archive.resume_panic();
+            if !error.is_null() {
+                let error = from_glib_full(error);
+                return Err(archive.client.error.take().map_or(error, |err| to_glib_error(&err)));
+            }
+        }
+        Ok(())
+    }
+
+    fn export_tree<T>(&self, options: &ExportArchiveOptions, root: &RepoFile, archive: &mut Archive<T>, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        let mut opts = RawExportArchiveOptions::from(options);
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_export_tree_to_archive(self.to_glib_none().0, &mut opts as *mut _ as *mut _, root.to_glib_none().0, archive.raw as *mut c_void, cancellable.to_glib_none().0, &mut error);
+            archive.resume_panic();
+            if !error.is_null() {
+                let error = from_glib_full(error);
+                return Err(archive.client.error.take().map_or(error, |err| to_glib_error(&err)));
+            }
+        }
+        let ret = unsafe { archive_write_close(archive.raw) };
+        archive.check(ret)
+    }
+}
@@ -0,0 +1,75 @@
+// This file was generated by gir (???) from gir-files (???)
+// DO NOT EDIT
+
+use ffi;
+use glib::translate::*;
+
+glib_wrapper! {
+    pub struct AsyncProgress(Object<ffi::OstreeAsyncProgress>);
+
+    match fn {
+        get_type => || ffi::ostree_async_progress_get_type(),
+    }
+}
+
+impl AsyncProgress {
+    pub fn new() -> AsyncProgress {
+        unsafe {
+            from_glib_full(ffi::ostree_async_progress_new())
+        }
+    }
+
+    //pub fn new_and_connect(changed: /*Unimplemented*/Option<Fundamental: Pointer>, user_data: /*Unimplemented*/Option<Fundamental: Pointer>) -> AsyncProgress {
+    //    unsafe { TODO: call ffi::ostree_async_progress_new_and_connect() }
+    //}
+
+    pub fn finish(&self) {
+        unsafe {
+            ffi::ostree_async_progress_finish(self.to_glib_none().0);
+        }
+    }
+
+    pub fn get_status(&self) -> Option<String> {
+        unsafe {
+            from_glib_full(ffi::ostree_async_progress_get_status(self.to_glib_none().0))
+        }
+    }
+
+    pub fn get_uint(&self, key: &str) -> u32 {
+        unsafe {
+            ffi::ostree_async_progress_get_uint(self.to_glib_none().0, key.to_glib_none().0)
+        }
+    }
+
+    pub fn get_uint64(&self, key: &str) -> u64 {
+        unsafe {
+            ffi::ostree_async_progress_get_uint64(self.to_glib_none().0, key.to_glib_none().0)
+        }
+    }
+
+    pub fn set_status<'a, P: Into<Option<&'a str>>>(&self, status: P) {
+        let status = status.into();
+        let status = status.to_glib_none();
+        unsafe {
+            ffi::ostree_async_progress_set_status(self.to_glib_none().0, status.0);
+        }
+    }
+
+    pub fn set_uint(&self, key: &str, value: u32) {
+        unsafe {
+            ffi::ostree_async_progress_set_uint(self.to_glib_none().0, key.to_glib_none().0, value);
+        }
+    }
+
+    pub fn set_uint64(&self, key: &str, value: u64) {
+        unsafe {
+            ffi::ostree_async_progress_set_uint64(self.to_glib_none().0, key.to_glib_none().0, value);
+        }
+    }
+}
+
+impl Default for AsyncProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,75 @@
+// This file was generated by gir (???) from gir-files (???)
+// DO NOT EDIT
+
+use Error;
+use ffi;
+use gio;
+use glib::object::IsA;
+use glib::translate::*;
+use std::ptr;
+
+glib_wrapper! {
+    pub struct BootconfigParser(Object<ffi::OstreeBootconfigParser>);
+
+    match fn {
+        get_type => || ffi::ostree_bootconfig_parser_get_type(),
+    }
+}
+
+impl BootconfigParser {
+    pub fn new() -> BootconfigParser {
+        unsafe {
+            from_glib_full(ffi::ostree_bootconfig_parser_new())
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<String> {
+        unsafe {
+            from_glib_none(ffi::ostree_bootconfig_parser_get(self.to_glib_none().0, key.to_glib_none().0))
+        }
+    }
+
+    pub fn parse<P: IsA<gio::File>>(&self, path: &P, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_bootconfig_parser_parse(self.to_glib_none().0, path.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn parse_at(&self, dfd: i32, path: &str, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_bootconfig_parser_parse_at(self.to_glib_none().0, dfd, path.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn set(&self, key: &str, value: &str) {
+        unsafe {
+            ffi::ostree_bootconfig_parser_set(self.to_glib_none().0, key.to_glib_none().0, value.to_glib_none().0);
+        }
+    }
+
+    pub fn write<P: IsA<gio::File>>(&self, output: &P, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_bootconfig_parser_write(self.to_glib_none().0, output.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn write_at(&self, dfd: i32, path: &str, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_bootconfig_parser_write_at(self.to_glib_none().0, dfd, path.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+}
+
+impl Default for BootconfigParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,13 @@
+// This file was generated by gir (???) from gir-files (???)
+// DO NOT EDIT
+
+use ffi;
+use gio;
+
+glib_wrapper! {
+    pub struct ChecksumInputStream(Object<ffi::OstreeChecksumInputStream>): gio::InputStream;
+
+    match fn {
+        get_type => || ffi::ostree_checksum_input_stream_get_type(),
+    }
+}
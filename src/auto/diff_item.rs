@@ -0,0 +1,13 @@
+// This file was generated by gir (???) from gir-files (???)
+// DO NOT EDIT
+
+use ffi;
+
+glib_wrapper! {
+    pub struct DiffItem(Shared<ffi::OstreeDiffItem>);
+
+    match fn {
+        ref => |ptr| ffi::ostree_diff_item_ref(ptr),
+        unref => |ptr| ffi::ostree_diff_item_unref(ptr),
+    }
+}
@@ -4,3 +4,79 @@
 use ffi;
 use glib::translate::*;
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum DeploymentUnlockedState {
+    None,
+    Development,
+    Hotfix,
+    #[doc(hidden)]
+    __Nonexhaustive(()),
+}
+
+#[doc(hidden)]
+impl ToGlib for DeploymentUnlockedState {
+    type GlibType = ffi::OstreeDeploymentUnlockedState;
+
+    fn to_glib(&self) -> ffi::OstreeDeploymentUnlockedState {
+        match *self {
+            DeploymentUnlockedState::None => ffi::OSTREE_DEPLOYMENT_UNLOCKED_NONE,
+            DeploymentUnlockedState::Development => ffi::OSTREE_DEPLOYMENT_UNLOCKED_DEVELOPMENT,
+            DeploymentUnlockedState::Hotfix => ffi::OSTREE_DEPLOYMENT_UNLOCKED_HOTFIX,
+            DeploymentUnlockedState::__Nonexhaustive(_) => panic!(),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::OstreeDeploymentUnlockedState> for DeploymentUnlockedState {
+    fn from_glib(value: ffi::OstreeDeploymentUnlockedState) -> Self {
+        match value {
+            ffi::OSTREE_DEPLOYMENT_UNLOCKED_NONE => DeploymentUnlockedState::None,
+            ffi::OSTREE_DEPLOYMENT_UNLOCKED_DEVELOPMENT => DeploymentUnlockedState::Development,
+            ffi::OSTREE_DEPLOYMENT_UNLOCKED_HOTFIX => DeploymentUnlockedState::Hotfix,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ObjectType {
+    File,
+    DirTree,
+    DirMeta,
+    Commit,
+    TombstoneCommit,
+    CommitMeta,
+    #[doc(hidden)]
+    __Nonexhaustive(()),
+}
+
+#[doc(hidden)]
+impl ToGlib for ObjectType {
+    type GlibType = ffi::OstreeObjectType;
+
+    fn to_glib(&self) -> ffi::OstreeObjectType {
+        match *self {
+            ObjectType::File => ffi::OSTREE_OBJECT_TYPE_FILE,
+            ObjectType::DirTree => ffi::OSTREE_OBJECT_TYPE_DIR_TREE,
+            ObjectType::DirMeta => ffi::OSTREE_OBJECT_TYPE_DIR_META,
+            ObjectType::Commit => ffi::OSTREE_OBJECT_TYPE_COMMIT,
+            ObjectType::TombstoneCommit => ffi::OSTREE_OBJECT_TYPE_TOMBSTONE_COMMIT,
+            ObjectType::CommitMeta => ffi::OSTREE_OBJECT_TYPE_COMMIT_META,
+            ObjectType::__Nonexhaustive(_) => panic!(),
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::OstreeObjectType> for ObjectType {
+    fn from_glib(value: ffi::OstreeObjectType) -> Self {
+        match value {
+            ffi::OSTREE_OBJECT_TYPE_FILE => ObjectType::File,
+            ffi::OSTREE_OBJECT_TYPE_DIR_TREE => ObjectType::DirTree,
+            ffi::OSTREE_OBJECT_TYPE_DIR_META => ObjectType::DirMeta,
+            ffi::OSTREE_OBJECT_TYPE_COMMIT => ObjectType::Commit,
+            ffi::OSTREE_OBJECT_TYPE_TOMBSTONE_COMMIT => ObjectType::TombstoneCommit,
+            ffi::OSTREE_OBJECT_TYPE_COMMIT_META => ObjectType::CommitMeta,
+        }
+    }
+}
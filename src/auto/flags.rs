@@ -4,3 +4,170 @@
 use ffi;
 use glib::translate::*;
 
+
+bitflags! {
+    pub flags DiffFlags: u32 {
+        const DIFF_FLAGS_NONE = 0,
+        const DIFF_FLAGS_IGNORE_XATTRS = 1,
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for DiffFlags {
+    type GlibType = ffi::OstreeDiffFlags;
+
+    fn to_glib(&self) -> ffi::OstreeDiffFlags {
+        ffi::OstreeDiffFlags::from_bits_truncate(self.bits())
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::OstreeDiffFlags> for DiffFlags {
+    fn from_glib(value: ffi::OstreeDiffFlags) -> DiffFlags {
+        DiffFlags::from_bits_truncate(value.bits())
+    }
+}
+
+bitflags! {
+    pub flags RepoCommitModifierFlags: u32 {
+        const REPO_COMMIT_MODIFIER_FLAGS_NONE = 0,
+        const REPO_COMMIT_MODIFIER_FLAGS_SKIP_XATTRS = 1,
+        const REPO_COMMIT_MODIFIER_FLAGS_GENERATE_SIZES = 2,
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for RepoCommitModifierFlags {
+    type GlibType = ffi::OstreeRepoCommitModifierFlags;
+
+    fn to_glib(&self) -> ffi::OstreeRepoCommitModifierFlags {
+        ffi::OstreeRepoCommitModifierFlags::from_bits_truncate(self.bits())
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::OstreeRepoCommitModifierFlags> for RepoCommitModifierFlags {
+    fn from_glib(value: ffi::OstreeRepoCommitModifierFlags) -> RepoCommitModifierFlags {
+        RepoCommitModifierFlags::from_bits_truncate(value.bits())
+    }
+}
+
+bitflags! {
+    pub flags RepoPullFlags: u32 {
+        const REPO_PULL_FLAGS_NONE = 0,
+        const REPO_PULL_FLAGS_MIRROR = 1,
+        const REPO_PULL_FLAGS_COMMIT_ONLY = 2,
+        const REPO_PULL_FLAGS_UNTRUSTED = 4,
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for RepoPullFlags {
+    type GlibType = ffi::OstreeRepoPullFlags;
+
+    fn to_glib(&self) -> ffi::OstreeRepoPullFlags {
+        ffi::OstreeRepoPullFlags::from_bits_truncate(self.bits())
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::OstreeRepoPullFlags> for RepoPullFlags {
+    fn from_glib(value: ffi::OstreeRepoPullFlags) -> RepoPullFlags {
+        RepoPullFlags::from_bits_truncate(value.bits())
+    }
+}
+
+bitflags! {
+    pub flags SePolicyRestoreconFlags: u32 {
+        const SEPOLICY_RESTORECON_FLAGS_NONE = 0,
+        const SEPOLICY_RESTORECON_FLAGS_ALLOW_NOLABEL = 1,
+        const SEPOLICY_RESTORECON_FLAGS_KEEP_EXISTING = 2,
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for SePolicyRestoreconFlags {
+    type GlibType = ffi::OstreeSePolicyRestoreconFlags;
+
+    fn to_glib(&self) -> ffi::OstreeSePolicyRestoreconFlags {
+        ffi::OstreeSePolicyRestoreconFlags::from_bits_truncate(self.bits())
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::OstreeSePolicyRestoreconFlags> for SePolicyRestoreconFlags {
+    fn from_glib(value: ffi::OstreeSePolicyRestoreconFlags) -> SePolicyRestoreconFlags {
+        SePolicyRestoreconFlags::from_bits_truncate(value.bits())
+    }
+}
+
+bitflags! {
+    pub flags SysrootSimpleWriteDeploymentFlags: u32 {
+        const SYSROOT_SIMPLE_WRITE_DEPLOYMENT_FLAGS_NONE = 0,
+        const SYSROOT_SIMPLE_WRITE_DEPLOYMENT_FLAGS_RETAIN = 1,
+        const SYSROOT_SIMPLE_WRITE_DEPLOYMENT_FLAGS_NOT_DEFAULT = 2,
+        const SYSROOT_SIMPLE_WRITE_DEPLOYMENT_FLAGS_NO_CLEAN = 4,
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for SysrootSimpleWriteDeploymentFlags {
+    type GlibType = ffi::OstreeSysrootSimpleWriteDeploymentFlags;
+
+    fn to_glib(&self) -> ffi::OstreeSysrootSimpleWriteDeploymentFlags {
+        ffi::OstreeSysrootSimpleWriteDeploymentFlags::from_bits_truncate(self.bits())
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::OstreeSysrootSimpleWriteDeploymentFlags> for SysrootSimpleWriteDeploymentFlags {
+    fn from_glib(value: ffi::OstreeSysrootSimpleWriteDeploymentFlags) -> SysrootSimpleWriteDeploymentFlags {
+        SysrootSimpleWriteDeploymentFlags::from_bits_truncate(value.bits())
+    }
+}
+
+bitflags! {
+    pub flags SysrootUpgraderFlags: u32 {
+        const SYSROOT_UPGRADER_FLAGS_IGNORE_UNCONFIGURED = 2,
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for SysrootUpgraderFlags {
+    type GlibType = ffi::OstreeSysrootUpgraderFlags;
+
+    fn to_glib(&self) -> ffi::OstreeSysrootUpgraderFlags {
+        ffi::OstreeSysrootUpgraderFlags::from_bits_truncate(self.bits())
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::OstreeSysrootUpgraderFlags> for SysrootUpgraderFlags {
+    fn from_glib(value: ffi::OstreeSysrootUpgraderFlags) -> SysrootUpgraderFlags {
+        SysrootUpgraderFlags::from_bits_truncate(value.bits())
+    }
+}
+
+bitflags! {
+    pub flags SysrootUpgraderPullFlags: u32 {
+        const SYSROOT_UPGRADER_PULL_FLAGS_NONE = 0,
+        const SYSROOT_UPGRADER_PULL_FLAGS_ALLOW_OLDER = 1,
+        const SYSROOT_UPGRADER_PULL_FLAGS_SYNTHETIC = 2,
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for SysrootUpgraderPullFlags {
+    type GlibType = ffi::OstreeSysrootUpgraderPullFlags;
+
+    fn to_glib(&self) -> ffi::OstreeSysrootUpgraderPullFlags {
+        ffi::OstreeSysrootUpgraderPullFlags::from_bits_truncate(self.bits())
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::OstreeSysrootUpgraderPullFlags> for SysrootUpgraderPullFlags {
+    fn from_glib(value: ffi::OstreeSysrootUpgraderPullFlags) -> SysrootUpgraderPullFlags {
+        SysrootUpgraderPullFlags::from_bits_truncate(value.bits())
+    }
+}
@@ -0,0 +1,65 @@
+// This file was generated by gir (???) from gir-files (???)
+// DO NOT EDIT
+
+use Error;
+use ffi;
+use glib;
+use glib::translate::*;
+use std::mem;
+use std::ptr;
+
+glib_wrapper! {
+    pub struct GpgVerifyResult(Object<ffi::OstreeGpgVerifyResult>);
+
+    match fn {
+        get_type => || ffi::ostree_gpg_verify_result_get_type(),
+    }
+}
+
+impl GpgVerifyResult {
+    //pub fn describe_variant<'a, T: Into<Option<&'a str>>>(variant: &glib::Variant, output_buffer: /*Ignored*/&mut glib::String, line_prefix: T, flags: /*Ignored*/GpgSignatureFormatFlags) {
+    //    unsafe { TODO: call ffi::ostree_gpg_verify_result_describe_variant() }
+    //}
+
+    pub fn count_all(&self) -> u32 {
+        unsafe {
+            ffi::ostree_gpg_verify_result_count_all(self.to_glib_none().0)
+        }
+    }
+
+    pub fn count_valid(&self) -> u32 {
+        unsafe {
+            ffi::ostree_gpg_verify_result_count_valid(self.to_glib_none().0)
+        }
+    }
+
+    //pub fn describe<'a, T: Into<Option<&'a str>>>(&self, signature_index: u32, output_buffer: /*Ignored*/&mut glib::String, line_prefix: T, flags: /*Ignored*/GpgSignatureFormatFlags) {
+    //    unsafe { TODO: call ffi::ostree_gpg_verify_result_describe() }
+    //}
+
+    //pub fn get(&self, signature_index: u32, attrs: /*Unimplemented*/&CArray TypeId { ns_id: 1, id: 2 }, n_attrs: u32) -> Option<glib::Variant> {
+    //    unsafe { TODO: call ffi::ostree_gpg_verify_result_get() }
+    //}
+
+    pub fn get_all(&self, signature_index: u32) -> Option<glib::Variant> {
+        unsafe {
+            from_glib_none(ffi::ostree_gpg_verify_result_get_all(self.to_glib_none().0, signature_index))
+        }
+    }
+
+    pub fn lookup(&self, key_id: &str) -> Option<u32> {
+        unsafe {
+            let mut out_signature_index = mem::uninitialized();
+            let ret = from_glib(ffi::ostree_gpg_verify_result_lookup(self.to_glib_none().0, key_id.to_glib_none().0, &mut out_signature_index));
+            if ret { Some(out_signature_index) } else { None }
+        }
+    }
+
+    pub fn require_valid_signature(&self) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_gpg_verify_result_require_valid_signature(self.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+}
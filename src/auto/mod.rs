@@ -1,12 +1,75 @@
 // This file was generated by gir (???) from gir-files (???)
 // DO NOT EDIT
 
+mod async_progress;
+pub use self::async_progress::AsyncProgress;
+
+mod bootconfig_parser;
+pub use self::bootconfig_parser::BootconfigParser;
+
+mod checksum_input_stream;
+pub use self::checksum_input_stream::ChecksumInputStream;
+
+mod diff_item;
+pub use self::diff_item::DiffItem;
+
+mod gpg_verify_result;
+pub use self::gpg_verify_result::GpgVerifyResult;
+
+mod mutable_tree;
+pub use self::mutable_tree::MutableTree;
+
 mod repo;
 pub use self::repo::Repo;
 
+mod repo_commit_modifier;
+pub use self::repo_commit_modifier::RepoCommitModifier;
+
 mod repo_file;
 pub use self::repo_file::RepoFile;
 
+mod se_policy;
+pub use self::se_policy::SePolicy;
+
+mod sysroot;
+pub use self::sysroot::Sysroot;
+
+mod sysroot_upgrader;
+pub use self::sysroot_upgrader::SysrootUpgrader;
+
+mod enums;
+pub use self::enums::DeploymentUnlockedState;
+pub use self::enums::ObjectType;
+
+mod flags;
+pub use self::flags::DiffFlags;
+pub use self::flags::DIFF_FLAGS_NONE;
+pub use self::flags::DIFF_FLAGS_IGNORE_XATTRS;
+pub use self::flags::RepoCommitModifierFlags;
+pub use self::flags::REPO_COMMIT_MODIFIER_FLAGS_NONE;
+pub use self::flags::REPO_COMMIT_MODIFIER_FLAGS_SKIP_XATTRS;
+pub use self::flags::REPO_COMMIT_MODIFIER_FLAGS_GENERATE_SIZES;
+pub use self::flags::RepoPullFlags;
+pub use self::flags::REPO_PULL_FLAGS_NONE;
+pub use self::flags::REPO_PULL_FLAGS_MIRROR;
+pub use self::flags::REPO_PULL_FLAGS_COMMIT_ONLY;
+pub use self::flags::REPO_PULL_FLAGS_UNTRUSTED;
+pub use self::flags::SePolicyRestoreconFlags;
+pub use self::flags::SEPOLICY_RESTORECON_FLAGS_NONE;
+pub use self::flags::SEPOLICY_RESTORECON_FLAGS_ALLOW_NOLABEL;
+pub use self::flags::SEPOLICY_RESTORECON_FLAGS_KEEP_EXISTING;
+pub use self::flags::SysrootSimpleWriteDeploymentFlags;
+pub use self::flags::SYSROOT_SIMPLE_WRITE_DEPLOYMENT_FLAGS_NONE;
+pub use self::flags::SYSROOT_SIMPLE_WRITE_DEPLOYMENT_FLAGS_RETAIN;
+pub use self::flags::SYSROOT_SIMPLE_WRITE_DEPLOYMENT_FLAGS_NOT_DEFAULT;
+pub use self::flags::SYSROOT_SIMPLE_WRITE_DEPLOYMENT_FLAGS_NO_CLEAN;
+pub use self::flags::SysrootUpgraderFlags;
+pub use self::flags::SYSROOT_UPGRADER_FLAGS_IGNORE_UNCONFIGURED;
+pub use self::flags::SysrootUpgraderPullFlags;
+pub use self::flags::SYSROOT_UPGRADER_PULL_FLAGS_NONE;
+pub use self::flags::SYSROOT_UPGRADER_PULL_FLAGS_ALLOW_OLDER;
+pub use self::flags::SYSROOT_UPGRADER_PULL_FLAGS_SYNTHETIC;
+
 #[doc(hidden)]
 pub mod traits {
 }
@@ -0,0 +1,90 @@
+// This file was generated by gir (???) from gir-files (???)
+// DO NOT EDIT
+
+use Error;
+use ffi;
+use glib::translate::*;
+use std::ptr;
+
+glib_wrapper! {
+    pub struct MutableTree(Object<ffi::OstreeMutableTree>);
+
+    match fn {
+        get_type => || ffi::ostree_mutable_tree_get_type(),
+    }
+}
+
+impl MutableTree {
+    pub fn new() -> MutableTree {
+        unsafe {
+            from_glib_full(ffi::ostree_mutable_tree_new())
+        }
+    }
+
+    pub fn ensure_dir(&self, name: &str) -> Result<MutableTree, Error> {
+        unsafe {
+            let mut out_subdir = ptr::null_mut();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_mutable_tree_ensure_dir(self.to_glib_none().0, name.to_glib_none().0, &mut out_subdir, &mut error);
+            if error.is_null() { Ok(from_glib_full(out_subdir)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    //pub fn ensure_parent_dirs(&self, split_path: /*Unknown conversion*//*Unimplemented*/PtrArray TypeId { ns_id: 0, id: 28 }, metadata_checksum: &str, error: /*Ignored*/Option<Error>) -> Result<MutableTree, Error> {
+    //    unsafe { TODO: call ffi::ostree_mutable_tree_ensure_parent_dirs() }
+    //}
+
+    pub fn get_contents_checksum(&self) -> Option<String> {
+        unsafe {
+            from_glib_none(ffi::ostree_mutable_tree_get_contents_checksum(self.to_glib_none().0))
+        }
+    }
+
+    //pub fn get_files(&self) -> /*Unknown conversion*//*Unimplemented*/HashTable TypeId { ns_id: 0, id: 28 }/TypeId { ns_id: 0, id: 28 } {
+    //    unsafe { TODO: call ffi::ostree_mutable_tree_get_files() }
+    //}
+
+    pub fn get_metadata_checksum(&self) -> Option<String> {
+        unsafe {
+            from_glib_none(ffi::ostree_mutable_tree_get_metadata_checksum(self.to_glib_none().0))
+        }
+    }
+
+    //pub fn get_subdirs(&self) -> /*Unknown conversion*//*Unimplemented*/HashTable TypeId { ns_id: 0, id: 28 }/TypeId { ns_id: 0, id: 28 } {
+    //    unsafe { TODO: call ffi::ostree_mutable_tree_get_subdirs() }
+    //}
+
+    //pub fn lookup(&self, name: &str, out_file_checksum: /*Unimplemented*/String, error: /*Ignored*/Option<Error>) -> Result<MutableTree, Error> {
+    //    unsafe { TODO: call ffi::ostree_mutable_tree_lookup() }
+    //}
+
+    pub fn replace_file(&self, name: &str, checksum: &str) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_mutable_tree_replace_file(self.to_glib_none().0, name.to_glib_none().0, checksum.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn set_contents_checksum(&self, checksum: &str) {
+        unsafe {
+            ffi::ostree_mutable_tree_set_contents_checksum(self.to_glib_none().0, checksum.to_glib_none().0);
+        }
+    }
+
+    pub fn set_metadata_checksum(&self, checksum: &str) {
+        unsafe {
+            ffi::ostree_mutable_tree_set_metadata_checksum(self.to_glib_none().0, checksum.to_glib_none().0);
+        }
+    }
+
+    //pub fn walk(&self, split_path: /*Unknown conversion*//*Unimplemented*/PtrArray TypeId { ns_id: 0, id: 28 }, start: u32, error: /*Ignored*/Option<Error>) -> Result<MutableTree, Error> {
+    //    unsafe { TODO: call ffi::ostree_mutable_tree_walk() }
+    //}
+}
+
+impl Default for MutableTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,11 +1,21 @@
 // This file was generated by gir (???) from gir-files (???)
 // DO NOT EDIT
 
+use Error;
+use GpgVerifyResult;
+use MutableTree;
+use ObjectType;
+use RepoCommitModifier;
+use RepoFile;
 use ffi;
+use gio;
+use glib;
 use glib::Value;
+use glib::object::IsA;
 use glib::translate::*;
 use gobject_ffi;
 use std::mem;
+use std::ptr;
 
 glib_wrapper! {
     pub struct Repo(Object<ffi::OstreeRepo>);
@@ -16,9 +26,11 @@ glib_wrapper! {
 }
 
 impl Repo {
-    //pub fn new<T: IsA</*Ignored*/gio::File>>(path: &T) -> Repo {
-    //    unsafe { TODO: call ffi::ostree_repo_new() }
-    //}
+    pub fn new<P: IsA<gio::File>>(path: &P) -> Repo {
+        unsafe {
+            from_glib_full(ffi::ostree_repo_new(path.to_glib_none().0))
+        }
+    }
 
     pub fn new_default() -> Repo {
         unsafe {
@@ -30,17 +42,21 @@ impl Repo {
     //    unsafe { TODO: call ffi::ostree_repo_new_for_sysroot_path() }
     //}
 
-    //pub fn abort_transaction(&self, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_abort_transaction() }
-    //}
-
-    //pub fn add_gpg_signature_summary<'a, T: Into<Option<&'a str>>>(&self, key_id: &[&str], homedir: T, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_add_gpg_signature_summary() }
-    //}
+    pub fn abort_transaction(&self, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_abort_transaction(self.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
 
-    //pub fn append_gpg_signature(&self, commit_checksum: &str, signature_bytes: /*Ignored*/&glib::Bytes, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_append_gpg_signature() }
-    //}
+    pub fn append_gpg_signature(&self, commit_checksum: &str, signature_bytes: &glib::Bytes, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_append_gpg_signature(self.to_glib_none().0, commit_checksum.to_glib_none().0, signature_bytes.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
 
     //pub fn checkout_at(&self, options: /*Ignored*/Option<&mut RepoCheckoutAtOptions>, destination_dfd: i32, destination_path: &str, commit: &str, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
     //    unsafe { TODO: call ffi::ostree_repo_checkout_at() }
@@ -58,10 +74,6 @@ impl Repo {
     //    unsafe { TODO: call ffi::ostree_repo_checkout_tree_at() }
     //}
 
-    //pub fn commit_transaction(&self, out_stats: /*Ignored*/RepoTransactionStats, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_commit_transaction() }
-    //}
-
     //pub fn copy_config(&self) -> /*Ignored*/Option<glib::KeyFile> {
     //    unsafe { TODO: call ffi::ostree_repo_copy_config() }
     //}
@@ -74,10 +86,6 @@ impl Repo {
     //    unsafe { TODO: call ffi::ostree_repo_delete_object() }
     //}
 
-    //pub fn export_tree_to_archive(&self, opts: /*Ignored*/&mut RepoExportArchiveOptions, root: &RepoFile, archive: /*Unimplemented*/Option<Fundamental: Pointer>, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_export_tree_to_archive() }
-    //}
-
     //pub fn get_config(&self) -> /*Ignored*/Option<glib::KeyFile> {
     //    unsafe { TODO: call ffi::ostree_repo_get_config() }
     //}
@@ -120,25 +128,40 @@ impl Repo {
     //    unsafe { TODO: call ffi::ostree_repo_get_remote_option() }
     //}
 
-    //pub fn gpg_verify_data<'a, T: Into<Option<&'a str>>, U: IsA</*Ignored*/gio::File>, V: IsA</*Ignored*/gio::File>>(&self, remote_name: T, data: /*Ignored*/&glib::Bytes, signatures: /*Ignored*/&glib::Bytes, keyringdir: Option<&U>, extra_keyring: Option<&V>, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> /*Ignored*/Option<GpgVerifyResult> {
-    //    unsafe { TODO: call ffi::ostree_repo_gpg_verify_data() }
-    //}
-
-    //pub fn has_object(&self, objtype: /*Ignored*/ObjectType, checksum: &str, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> Result<(bool), Error> {
-    //    unsafe { TODO: call ffi::ostree_repo_has_object() }
-    //}
+    pub fn gpg_verify_data<'a, T: Into<Option<&'a str>>, U: IsA<gio::File>, V: IsA<gio::File>>(&self, remote_name: T, data: &glib::Bytes, signatures: &glib::Bytes, keyringdir: Option<&U>, extra_keyring: Option<&V>, cancellable: Option<&gio::Cancellable>) -> Result<GpgVerifyResult, Error> {
+        let remote_name = remote_name.into();
+        let remote_name = remote_name.to_glib_none();
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = ffi::ostree_repo_gpg_verify_data(self.to_glib_none().0, remote_name.0, data.to_glib_none().0, signatures.to_glib_none().0, keyringdir.to_glib_none().0, extra_keyring.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(ret)) } else { Err(from_glib_full(error)) }
+        }
+    }
 
-    //pub fn import_archive_to_mtree(&self, opts: /*Ignored*/&mut RepoImportArchiveOptions, archive: /*Unimplemented*/Option<Fundamental: Pointer>, mtree: /*Ignored*/&MutableTree, modifier: /*Ignored*/Option<&RepoCommitModifier>, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_import_archive_to_mtree() }
-    //}
+    pub fn has_object(&self, objtype: ObjectType, checksum: &str, cancellable: Option<&gio::Cancellable>) -> Result<bool, Error> {
+        unsafe {
+            let mut out_have_object = mem::uninitialized();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_has_object(self.to_glib_none().0, objtype.to_glib(), checksum.to_glib_none().0, &mut out_have_object, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib(out_have_object)) } else { Err(from_glib_full(error)) }
+        }
+    }
 
-    //pub fn import_object_from(&self, source: &Repo, objtype: /*Ignored*/ObjectType, checksum: &str, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_import_object_from() }
-    //}
+    pub fn import_object_from(&self, source: &Repo, objtype: ObjectType, checksum: &str, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_import_object_from(self.to_glib_none().0, source.to_glib_none().0, objtype.to_glib(), checksum.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
 
-    //pub fn import_object_from_with_trust(&self, source: &Repo, objtype: /*Ignored*/ObjectType, checksum: &str, trusted: bool, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_import_object_from_with_trust() }
-    //}
+    pub fn import_object_from_with_trust(&self, source: &Repo, objtype: ObjectType, checksum: &str, trusted: bool, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_import_object_from_with_trust(self.to_glib_none().0, source.to_glib_none().0, objtype.to_glib(), checksum.to_glib_none().0, trusted.to_glib(), cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
 
     pub fn is_system(&self) -> bool {
         unsafe {
@@ -174,13 +197,15 @@ impl Repo {
     //    unsafe { TODO: call ffi::ostree_repo_load_commit() }
     //}
 
-    //pub fn load_file(&self, checksum: &str, out_input: /*Ignored*/gio::InputStream, out_file_info: /*Ignored*/gio::FileInfo, out_xattrs: /*Ignored*/glib::Variant, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_load_file() }
-    //}
-
-    //pub fn load_object_stream(&self, objtype: /*Ignored*/ObjectType, checksum: &str, out_input: /*Ignored*/gio::InputStream, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> Result<(u64), Error> {
-    //    unsafe { TODO: call ffi::ostree_repo_load_object_stream() }
-    //}
+    pub fn load_object_stream(&self, objtype: ObjectType, checksum: &str, cancellable: Option<&gio::Cancellable>) -> Result<(gio::InputStream, u64), Error> {
+        unsafe {
+            let mut out_input = ptr::null_mut();
+            let mut out_size = mem::uninitialized();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_load_object_stream(self.to_glib_none().0, objtype.to_glib(), checksum.to_glib_none().0, &mut out_input, &mut out_size, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok((from_glib_full(out_input), out_size)) } else { Err(from_glib_full(error)) }
+        }
+    }
 
     //pub fn load_variant(&self, objtype: /*Ignored*/ObjectType, sha256: &str, out_variant: /*Ignored*/glib::Variant, error: /*Ignored*/Option<Error>) -> bool {
     //    unsafe { TODO: call ffi::ostree_repo_load_variant() }
@@ -190,13 +215,22 @@ impl Repo {
     //    unsafe { TODO: call ffi::ostree_repo_load_variant_if_exists() }
     //}
 
-    //pub fn open(&self, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_open() }
-    //}
+    pub fn open(&self, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_open(self.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
 
-    //pub fn prepare_transaction(&self, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> Result<(bool), Error> {
-    //    unsafe { TODO: call ffi::ostree_repo_prepare_transaction() }
-    //}
+    pub fn prepare_transaction(&self, cancellable: Option<&gio::Cancellable>) -> Result<bool, Error> {
+        unsafe {
+            let mut out_transaction_resume = mem::uninitialized();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_prepare_transaction(self.to_glib_none().0, &mut out_transaction_resume, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib(out_transaction_resume)) } else { Err(from_glib_full(error)) }
+        }
+    }
 
     //pub fn prune(&self, flags: /*Ignored*/RepoPruneFlags, depth: i32, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> Result<(i32, i32, u64), Error> {
     //    unsafe { TODO: call ffi::ostree_repo_prune() }
@@ -222,17 +256,33 @@ impl Repo {
     //    unsafe { TODO: call ffi::ostree_repo_pull_with_options() }
     //}
 
-    //pub fn query_object_storage_size(&self, objtype: /*Ignored*/ObjectType, sha256: &str, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> Result<(u64), Error> {
-    //    unsafe { TODO: call ffi::ostree_repo_query_object_storage_size() }
-    //}
+    pub fn query_object_storage_size(&self, objtype: ObjectType, sha256: &str, cancellable: Option<&gio::Cancellable>) -> Result<u64, Error> {
+        unsafe {
+            let mut out_size = mem::uninitialized();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_query_object_storage_size(self.to_glib_none().0, objtype.to_glib(), sha256.to_glib_none().0, &mut out_size, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(out_size) } else { Err(from_glib_full(error)) }
+        }
+    }
 
-    //pub fn read_commit(&self, ref_: &str, out_root: /*Ignored*/gio::File, out_commit: /*Unimplemented*/String, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_read_commit() }
-    //}
+    pub fn read_commit(&self, ref_: &str, cancellable: Option<&gio::Cancellable>) -> Result<(gio::File, String), Error> {
+        unsafe {
+            let mut out_root = ptr::null_mut();
+            let mut out_commit = ptr::null_mut();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_read_commit(self.to_glib_none().0, ref_.to_glib_none().0, &mut out_root, &mut out_commit, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok((from_glib_full(out_root), from_glib_full(out_commit))) } else { Err(from_glib_full(error)) }
+        }
+    }
 
-    //pub fn read_commit_detached_metadata(&self, checksum: &str, out_metadata: /*Ignored*/glib::Variant, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_read_commit_detached_metadata() }
-    //}
+    pub fn read_commit_detached_metadata(&self, checksum: &str, cancellable: Option<&gio::Cancellable>) -> Result<Option<glib::Variant>, Error> {
+        unsafe {
+            let mut out_metadata = ptr::null_mut();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_read_commit_detached_metadata(self.to_glib_none().0, checksum.to_glib_none().0, &mut out_metadata, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(out_metadata)) } else { Err(from_glib_full(error)) }
+        }
+    }
 
     //pub fn regenerate_summary(&self, additional_metadata: /*Ignored*/Option<&glib::Variant>, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
     //    unsafe { TODO: call ffi::ostree_repo_regenerate_summary() }
@@ -274,10 +324,6 @@ impl Repo {
     //    unsafe { TODO: call ffi::ostree_repo_remote_get_url() }
     //}
 
-    //pub fn remote_gpg_import<T: IsA</*Ignored*/gio::InputStream>>(&self, name: &str, source_stream: Option<&T>, key_ids: &[&str], out_imported: u32, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_remote_gpg_import() }
-    //}
-
     pub fn remote_list(&self) -> (Vec<String>, u32) {
         unsafe {
             let mut out_n_remotes = mem::uninitialized();
@@ -316,14 +362,6 @@ impl Repo {
     //    unsafe { TODO: call ffi::ostree_repo_set_ref_immediate() }
     //}
 
-    //pub fn sign_commit<'a, T: Into<Option<&'a str>>>(&self, commit_checksum: &str, key_id: &str, homedir: T, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_sign_commit() }
-    //}
-
-    //pub fn sign_delta(&self, from_commit: &str, to_commit: &str, key_id: &str, homedir: &str, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_sign_delta() }
-    //}
-
     //pub fn static_delta_execute_offline<T: IsA</*Ignored*/gio::File>>(&self, dir_or_file: &T, skip_validation: bool, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
     //    unsafe { TODO: call ffi::ostree_repo_static_delta_execute_offline() }
     //}
@@ -356,29 +394,60 @@ impl Repo {
     //    unsafe { TODO: call ffi::ostree_repo_verify_commit() }
     //}
 
-    //pub fn verify_commit_ext<T: IsA</*Ignored*/gio::File>, U: IsA</*Ignored*/gio::File>>(&self, commit_checksum: &str, keyringdir: Option<&T>, extra_keyring: Option<&U>, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> /*Ignored*/Option<GpgVerifyResult> {
-    //    unsafe { TODO: call ffi::ostree_repo_verify_commit_ext() }
-    //}
+    pub fn verify_commit_ext<T: IsA<gio::File>, U: IsA<gio::File>>(&self, commit_checksum: &str, keyringdir: Option<&T>, extra_keyring: Option<&U>, cancellable: Option<&gio::Cancellable>) -> Result<GpgVerifyResult, Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = ffi::ostree_repo_verify_commit_ext(self.to_glib_none().0, commit_checksum.to_glib_none().0, keyringdir.to_glib_none().0, extra_keyring.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(ret)) } else { Err(from_glib_full(error)) }
+        }
+    }
 
-    //pub fn verify_commit_for_remote(&self, commit_checksum: &str, remote_name: &str, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> /*Ignored*/Option<GpgVerifyResult> {
-    //    unsafe { TODO: call ffi::ostree_repo_verify_commit_for_remote() }
-    //}
+    pub fn verify_commit_for_remote(&self, commit_checksum: &str, remote_name: &str, cancellable: Option<&gio::Cancellable>) -> Result<GpgVerifyResult, Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = ffi::ostree_repo_verify_commit_for_remote(self.to_glib_none().0, commit_checksum.to_glib_none().0, remote_name.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(ret)) } else { Err(from_glib_full(error)) }
+        }
+    }
 
-    //pub fn verify_summary(&self, remote_name: &str, summary: /*Ignored*/&glib::Bytes, signatures: /*Ignored*/&glib::Bytes, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> /*Ignored*/Option<GpgVerifyResult> {
-    //    unsafe { TODO: call ffi::ostree_repo_verify_summary() }
-    //}
+    pub fn verify_summary(&self, remote_name: &str, summary: &glib::Bytes, signatures: &glib::Bytes, cancellable: Option<&gio::Cancellable>) -> Result<GpgVerifyResult, Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = ffi::ostree_repo_verify_summary(self.to_glib_none().0, remote_name.to_glib_none().0, summary.to_glib_none().0, signatures.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(ret)) } else { Err(from_glib_full(error)) }
+        }
+    }
 
-    //pub fn write_archive_to_mtree<T: IsA</*Ignored*/gio::File>>(&self, archive: &T, mtree: /*Ignored*/&MutableTree, modifier: /*Ignored*/Option<&RepoCommitModifier>, autocreate_parents: bool, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_write_archive_to_mtree() }
-    //}
+    pub fn write_archive_to_mtree<T: IsA<gio::File>>(&self, archive: &T, mtree: &MutableTree, modifier: Option<&RepoCommitModifier>, autocreate_parents: bool, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_write_archive_to_mtree(self.to_glib_none().0, archive.to_glib_none().0, mtree.to_glib_none().0, modifier.to_glib_none().0, autocreate_parents.to_glib(), cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
 
-    //pub fn write_commit<'a, 'b, 'c, T: Into<Option<&'a str>>, U: Into<Option<&'b str>>, V: Into<Option<&'c str>>>(&self, parent: T, subject: U, body: V, metadata: /*Ignored*/Option<&glib::Variant>, root: &RepoFile, out_commit: /*Unimplemented*/String, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_write_commit() }
-    //}
+    pub fn write_commit<'a, 'b, 'c, T: Into<Option<&'a str>>, U: Into<Option<&'b str>>, V: Into<Option<&'c str>>>(&self, parent: T, subject: U, body: V, metadata: Option<&glib::Variant>, root: &RepoFile, cancellable: Option<&gio::Cancellable>) -> Result<String, Error> {
+        let parent = parent.into();
+        let parent = parent.to_glib_none();
+        let subject = subject.into();
+        let subject = subject.to_glib_none();
+        let body = body.into();
+        let body = body.to_glib_none();
+        unsafe {
+            let mut out_commit = ptr::null_mut();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_write_commit(self.to_glib_none().0, parent.0, subject.0, body.0, metadata.to_glib_none().0, root.to_glib_none().0, &mut out_commit, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(out_commit)) } else { Err(from_glib_full(error)) }
+        }
+    }
 
-    //pub fn write_commit_detached_metadata(&self, checksum: &str, metadata: /*Ignored*/Option<&glib::Variant>, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_write_commit_detached_metadata() }
-    //}
+    pub fn write_commit_detached_metadata(&self, checksum: &str, metadata: Option<&glib::Variant>, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_write_commit_detached_metadata(self.to_glib_none().0, checksum.to_glib_none().0, metadata.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
 
     //pub fn write_commit_with_time<'a, 'b, 'c, T: Into<Option<&'a str>>, U: Into<Option<&'b str>>, V: Into<Option<&'c str>>>(&self, parent: T, subject: U, body: V, metadata: /*Ignored*/Option<&glib::Variant>, root: &RepoFile, time: u64, out_commit: /*Unimplemented*/String, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
     //    unsafe { TODO: call ffi::ostree_repo_write_commit_with_time() }
@@ -388,21 +457,13 @@ impl Repo {
     //    unsafe { TODO: call ffi::ostree_repo_write_config() }
     //}
 
-    //pub fn write_content<'a, T: Into<Option<&'a str>>, U: IsA</*Ignored*/gio::InputStream>>(&self, expected_checksum: T, object_input: &U, length: u64, out_csum: /*Unknown conversion*//*Unimplemented*/FixedArray TypeId { ns_id: 0, id: 3 }; 32, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_write_content() }
-    //}
-
-    //pub fn write_content_async<'a, T: Into<Option<&'a str>>, U: IsA</*Ignored*/gio::InputStream>>(&self, expected_checksum: T, object: &U, length: u64, cancellable: /*Ignored*/Option<&gio::Cancellable>, callback: /*Unknown conversion*//*Unimplemented*/AsyncReadyCallback, user_data: /*Unimplemented*/Option<Fundamental: Pointer>) {
-    //    unsafe { TODO: call ffi::ostree_repo_write_content_async() }
-    //}
-
-    //pub fn write_content_finish<T: IsA</*Ignored*/gio::AsyncResult>>(&self, result: &T, error: /*Ignored*/Option<Error>) -> Result<(u8), Error> {
-    //    unsafe { TODO: call ffi::ostree_repo_write_content_finish() }
-    //}
-
-    //pub fn write_content_trusted<T: IsA</*Ignored*/gio::InputStream>>(&self, checksum: &str, object_input: &T, length: u64, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_write_content_trusted() }
-    //}
+    pub fn write_content_trusted<T: IsA<gio::InputStream>>(&self, checksum: &str, object_input: &T, length: u64, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_write_content_trusted(self.to_glib_none().0, checksum.to_glib_none().0, object_input.to_glib_none().0, length, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
 
     //pub fn write_dfd_to_mtree(&self, dfd: i32, path: &str, mtree: /*Ignored*/&MutableTree, modifier: /*Ignored*/Option<&RepoCommitModifier>, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
     //    unsafe { TODO: call ffi::ostree_repo_write_dfd_to_mtree() }
@@ -412,29 +473,30 @@ impl Repo {
     //    unsafe { TODO: call ffi::ostree_repo_write_directory_to_mtree() }
     //}
 
-    //pub fn write_metadata<'a, T: Into<Option<&'a str>>>(&self, objtype: /*Ignored*/ObjectType, expected_checksum: T, object: /*Ignored*/&glib::Variant, out_csum: /*Unknown conversion*//*Unimplemented*/FixedArray TypeId { ns_id: 0, id: 3 }; 32, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_write_metadata() }
-    //}
-
-    //pub fn write_metadata_async<'a, T: Into<Option<&'a str>>>(&self, objtype: /*Ignored*/ObjectType, expected_checksum: T, object: /*Ignored*/&glib::Variant, cancellable: /*Ignored*/Option<&gio::Cancellable>, callback: /*Unknown conversion*//*Unimplemented*/AsyncReadyCallback, user_data: /*Unimplemented*/Option<Fundamental: Pointer>) {
-    //    unsafe { TODO: call ffi::ostree_repo_write_metadata_async() }
-    //}
-
-    //pub fn write_metadata_finish<T: IsA</*Ignored*/gio::AsyncResult>>(&self, result: &T, out_csum: u8, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_write_metadata_finish() }
-    //}
-
-    //pub fn write_metadata_stream_trusted<T: IsA</*Ignored*/gio::InputStream>>(&self, objtype: /*Ignored*/ObjectType, checksum: &str, object_input: &T, length: u64, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_write_metadata_stream_trusted() }
-    //}
+    pub fn write_metadata_stream_trusted<T: IsA<gio::InputStream>>(&self, objtype: ObjectType, checksum: &str, object_input: &T, length: u64, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_write_metadata_stream_trusted(self.to_glib_none().0, objtype.to_glib(), checksum.to_glib_none().0, object_input.to_glib_none().0, length, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
 
-    //pub fn write_metadata_trusted(&self, objtype: /*Ignored*/ObjectType, checksum: &str, variant: /*Ignored*/&glib::Variant, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_write_metadata_trusted() }
-    //}
+    pub fn write_metadata_trusted(&self, objtype: ObjectType, checksum: &str, variant: &glib::Variant, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_write_metadata_trusted(self.to_glib_none().0, objtype.to_glib(), checksum.to_glib_none().0, variant.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
 
-    //pub fn write_mtree(&self, mtree: /*Ignored*/&MutableTree, out_file: /*Ignored*/gio::File, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_write_mtree() }
-    //}
+    pub fn write_mtree(&self, mtree: &MutableTree, cancellable: Option<&gio::Cancellable>) -> Result<gio::File, Error> {
+        unsafe {
+            let mut out_file = ptr::null_mut();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_write_mtree(self.to_glib_none().0, mtree.to_glib_none().0, &mut out_file, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(out_file)) } else { Err(from_glib_full(error)) }
+        }
+    }
 
     //pub fn set_property_path(&self, path: /*Ignored*/Option<&gio::File>) {
     //    unsafe {
@@ -0,0 +1,33 @@
+// This file was generated by gir (???) from gir-files (???)
+// DO NOT EDIT
+
+use SePolicy;
+use ffi;
+use glib::translate::*;
+
+glib_wrapper! {
+    pub struct RepoCommitModifier(Shared<ffi::OstreeRepoCommitModifier>);
+
+    match fn {
+        ref => |ptr| ffi::ostree_repo_commit_modifier_ref(ptr),
+        unref => |ptr| ffi::ostree_repo_commit_modifier_unref(ptr),
+    }
+}
+
+impl RepoCommitModifier {
+    //pub fn set_devino_cache(&self, cache: /*Ignored*/&RepoDevInoCache) {
+    //    unsafe { TODO: call ffi::ostree_repo_commit_modifier_set_devino_cache() }
+    //}
+
+    pub fn set_sepolicy<'a, P: Into<Option<&'a SePolicy>>>(&self, sepolicy: P) {
+        let sepolicy = sepolicy.into();
+        let sepolicy = sepolicy.to_glib_none();
+        unsafe {
+            ffi::ostree_repo_commit_modifier_set_sepolicy(self.to_glib_none().0, sepolicy.0);
+        }
+    }
+
+    //pub fn set_xattr_callback<P: Fn(&Repo, &str, &gio::FileInfo) -> glib::Variant + 'static>(&self, callback: P) {
+    //    unsafe { TODO: call ffi::ostree_repo_commit_modifier_set_xattr_callback() }
+    //}
+}
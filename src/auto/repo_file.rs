@@ -1,12 +1,16 @@
 // This file was generated by gir (???) from gir-files (???)
 // DO NOT EDIT
 
+use Error;
 use Repo;
 use ffi;
+use gio;
+use glib;
 use glib::translate::*;
+use std::ptr;
 
 glib_wrapper! {
-    pub struct RepoFile(Object<ffi::OstreeRepoFile>);
+    pub struct RepoFile(Object<ffi::OstreeRepoFile>): gio::File;
 
     match fn {
         get_type => || ffi::ostree_repo_file_get_type(),
@@ -14,9 +18,13 @@ glib_wrapper! {
 }
 
 impl RepoFile {
-    //pub fn ensure_resolved(&self, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_file_ensure_resolved() }
-    //}
+    pub fn ensure_resolved(&self) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_file_ensure_resolved(self.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
 
     pub fn get_checksum(&self) -> Option<String> {
         unsafe {
@@ -36,17 +44,11 @@ impl RepoFile {
         }
     }
 
-    //pub fn get_xattrs(&self, out_xattrs: /*Ignored*/&glib::Variant, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_file_get_xattrs() }
-    //}
-
-    //pub fn tree_find_child(&self, name: &str, is_dir: bool, out_container: /*Ignored*/&glib::Variant) -> i32 {
-    //    unsafe { TODO: call ffi::ostree_repo_file_tree_find_child() }
-    //}
-
-    //pub fn tree_get_contents(&self) -> /*Ignored*/Option<glib::Variant> {
-    //    unsafe { TODO: call ffi::ostree_repo_file_tree_get_contents() }
-    //}
+    pub fn tree_get_contents(&self) -> Option<glib::Variant> {
+        unsafe {
+            from_glib_none(ffi::ostree_repo_file_tree_get_contents(self.to_glib_none().0))
+        }
+    }
 
     pub fn tree_get_contents_checksum(&self) -> Option<String> {
         unsafe {
@@ -54,9 +56,11 @@ impl RepoFile {
         }
     }
 
-    //pub fn tree_get_metadata(&self) -> /*Ignored*/Option<glib::Variant> {
-    //    unsafe { TODO: call ffi::ostree_repo_file_tree_get_metadata() }
-    //}
+    pub fn tree_get_metadata(&self) -> Option<glib::Variant> {
+        unsafe {
+            from_glib_none(ffi::ostree_repo_file_tree_get_metadata(self.to_glib_none().0))
+        }
+    }
 
     pub fn tree_get_metadata_checksum(&self) -> Option<String> {
         unsafe {
@@ -64,11 +68,18 @@ impl RepoFile {
         }
     }
 
-    //pub fn tree_query_child(&self, n: i32, attributes: &str, flags: /*Ignored*/gio::FileQueryInfoFlags, out_info: /*Ignored*/&gio::FileInfo, cancellable: /*Ignored*/Option<&gio::Cancellable>, error: /*Ignored*/Option<Error>) -> bool {
-    //    unsafe { TODO: call ffi::ostree_repo_file_tree_query_child() }
-    //}
+    pub fn tree_query_child(&self, n: i32, attributes: &str, flags: gio::FileQueryInfoFlags, cancellable: Option<&gio::Cancellable>) -> Result<Option<gio::FileInfo>, Error> {
+        unsafe {
+            let mut out_info = ptr::null_mut();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_file_tree_query_child(self.to_glib_none().0, n, attributes.to_glib_none().0, flags.to_glib(), &mut out_info, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(out_info)) } else { Err(from_glib_full(error)) }
+        }
+    }
 
-    //pub fn tree_set_metadata(&self, checksum: &str, metadata: /*Ignored*/&glib::Variant) {
-    //    unsafe { TODO: call ffi::ostree_repo_file_tree_set_metadata() }
-    //}
+    pub fn tree_set_metadata(&self, checksum: &str, metadata: &glib::Variant) {
+        unsafe {
+            ffi::ostree_repo_file_tree_set_metadata(self.to_glib_none().0, checksum.to_glib_none().0, metadata.to_glib_none().0);
+        }
+    }
 }
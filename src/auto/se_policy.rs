@@ -0,0 +1,72 @@
+// This file was generated by gir (???) from gir-files (???)
+// DO NOT EDIT
+
+use Error;
+use SePolicyRestoreconFlags;
+use ffi;
+use gio;
+use glib::object::IsA;
+use glib::translate::*;
+use std::ptr;
+
+glib_wrapper! {
+    pub struct SePolicy(Object<ffi::OstreeSePolicy>);
+
+    match fn {
+        get_type => || ffi::ostree_sepolicy_get_type(),
+    }
+}
+
+impl SePolicy {
+    pub fn new<P: IsA<gio::File>>(path: &P, cancellable: Option<&gio::Cancellable>) -> Result<SePolicy, Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = ffi::ostree_sepolicy_new(path.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(ret)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn get_csum(&self) -> Option<String> {
+        unsafe {
+            from_glib_none(ffi::ostree_sepolicy_get_csum(self.to_glib_none().0))
+        }
+    }
+
+    pub fn get_label(&self, relpath: &str, unix_mode: u32, cancellable: Option<&gio::Cancellable>) -> Result<Option<String>, Error> {
+        unsafe {
+            let mut out_label = ptr::null_mut();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sepolicy_get_label(self.to_glib_none().0, relpath.to_glib_none().0, unix_mode, &mut out_label, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(out_label)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn get_name(&self) -> Option<String> {
+        unsafe {
+            from_glib_none(ffi::ostree_sepolicy_get_name(self.to_glib_none().0))
+        }
+    }
+
+    pub fn get_path(&self) -> Option<gio::File> {
+        unsafe {
+            from_glib_none(ffi::ostree_sepolicy_get_path(self.to_glib_none().0))
+        }
+    }
+
+    pub fn restorecon<P: IsA<gio::File>>(&self, path: &str, info: Option<&gio::FileInfo>, target: &P, flags: SePolicyRestoreconFlags, cancellable: Option<&gio::Cancellable>) -> Result<Option<String>, Error> {
+        unsafe {
+            let mut out_new_label = ptr::null_mut();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sepolicy_restorecon(self.to_glib_none().0, path.to_glib_none().0, info.to_glib_none().0, target.to_glib_none().0, flags.to_glib(), &mut out_new_label, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(out_new_label)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn setfscreatecon(&self, path: &str, mode: u32) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sepolicy_setfscreatecon(self.to_glib_none().0, path.to_glib_none().0, mode, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+}
@@ -0,0 +1,235 @@
+// This file was generated by gir (???) from gir-files (???)
+// DO NOT EDIT
+
+use Deployment;
+use DeploymentUnlockedState;
+use Error;
+use Repo;
+use SysrootSimpleWriteDeploymentFlags;
+use ffi;
+use gio;
+use glib;
+use glib::object::IsA;
+use glib::translate::*;
+use std::mem;
+use std::ptr;
+
+glib_wrapper! {
+    pub struct Sysroot(Object<ffi::OstreeSysroot>);
+
+    match fn {
+        get_type => || ffi::ostree_sysroot_get_type(),
+    }
+}
+
+impl Sysroot {
+    pub fn new<P: IsA<gio::File>>(path: Option<&P>) -> Sysroot {
+        unsafe {
+            from_glib_full(ffi::ostree_sysroot_new(path.to_glib_none().0))
+        }
+    }
+
+    pub fn new_default() -> Sysroot {
+        unsafe {
+            from_glib_full(ffi::ostree_sysroot_new_default())
+        }
+    }
+
+    pub fn get_deployment_origin_path<P: IsA<gio::File>>(deployment_path: &P) -> Option<gio::File> {
+        unsafe {
+            from_glib_full(ffi::ostree_sysroot_get_deployment_origin_path(deployment_path.to_glib_none().0))
+        }
+    }
+
+    pub fn cleanup(&self, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_cleanup(self.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn deploy_tree<'a, 'b, P: Into<Option<&'a str>>, Q: Into<Option<&'b Deployment>>>(&self, osname: P, revision: &str, origin: Option<&glib::KeyFile>, provided_merge_deployment: Q, override_kernel_argv: Option<&[&str]>, cancellable: Option<&gio::Cancellable>) -> Result<Deployment, Error> {
+        let osname = osname.into();
+        let osname = osname.to_glib_none();
+        let provided_merge_deployment = provided_merge_deployment.into();
+        let provided_merge_deployment = provided_merge_deployment.to_glib_none();
+        unsafe {
+            let mut out_new_deployment = ptr::null_mut();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_deploy_tree(self.to_glib_none().0, osname.0, revision.to_glib_none().0, origin.to_glib_none().0, provided_merge_deployment.0, override_kernel_argv.to_glib_none().0, &mut out_new_deployment, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(out_new_deployment)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn deployment_set_mutable(&self, deployment: &Deployment, is_mutable: bool, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_deployment_set_mutable(self.to_glib_none().0, deployment.to_glib_none().0, is_mutable.to_glib(), cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn deployment_unlock(&self, deployment: &Deployment, unlocked_state: DeploymentUnlockedState, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_deployment_unlock(self.to_glib_none().0, deployment.to_glib_none().0, unlocked_state.to_glib(), cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn ensure_initialized(&self, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_ensure_initialized(self.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn get_booted_deployment(&self) -> Option<Deployment> {
+        unsafe {
+            from_glib_none(ffi::ostree_sysroot_get_booted_deployment(self.to_glib_none().0))
+        }
+    }
+
+    pub fn get_bootversion(&self) -> i32 {
+        unsafe {
+            ffi::ostree_sysroot_get_bootversion(self.to_glib_none().0)
+        }
+    }
+
+    pub fn get_deployment_directory(&self, deployment: &Deployment) -> Option<gio::File> {
+        unsafe {
+            from_glib_full(ffi::ostree_sysroot_get_deployment_directory(self.to_glib_none().0, deployment.to_glib_none().0))
+        }
+    }
+
+    pub fn get_deployment_dirpath(&self, deployment: &Deployment) -> Option<String> {
+        unsafe {
+            from_glib_full(ffi::ostree_sysroot_get_deployment_dirpath(self.to_glib_none().0, deployment.to_glib_none().0))
+        }
+    }
+
+    pub fn get_fd(&self) -> i32 {
+        unsafe {
+            ffi::ostree_sysroot_get_fd(self.to_glib_none().0)
+        }
+    }
+
+    pub fn get_merge_deployment<'a, P: Into<Option<&'a str>>>(&self, osname: P) -> Option<Deployment> {
+        let osname = osname.into();
+        let osname = osname.to_glib_none();
+        unsafe {
+            from_glib_full(ffi::ostree_sysroot_get_merge_deployment(self.to_glib_none().0, osname.0))
+        }
+    }
+
+    pub fn get_path(&self) -> Option<gio::File> {
+        unsafe {
+            from_glib_none(ffi::ostree_sysroot_get_path(self.to_glib_none().0))
+        }
+    }
+
+    pub fn get_repo(&self, cancellable: Option<&gio::Cancellable>) -> Result<Repo, Error> {
+        unsafe {
+            let mut out_repo = ptr::null_mut();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_get_repo(self.to_glib_none().0, &mut out_repo, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(out_repo)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn get_subbootversion(&self) -> i32 {
+        unsafe {
+            ffi::ostree_sysroot_get_subbootversion(self.to_glib_none().0)
+        }
+    }
+
+    pub fn init_osname(&self, osname: &str, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_init_osname(self.to_glib_none().0, osname.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn load(&self, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_load(self.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn load_if_changed(&self, cancellable: Option<&gio::Cancellable>) -> Result<bool, Error> {
+        unsafe {
+            let mut out_changed = mem::uninitialized();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_load_if_changed(self.to_glib_none().0, &mut out_changed, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib(out_changed)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn lock(&self) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_lock(self.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn origin_new_from_refspec(&self, refspec: &str) -> Option<glib::KeyFile> {
+        unsafe {
+            from_glib_full(ffi::ostree_sysroot_origin_new_from_refspec(self.to_glib_none().0, refspec.to_glib_none().0))
+        }
+    }
+
+    pub fn prepare_cleanup(&self, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_prepare_cleanup(self.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn simple_write_deployment<'a, 'b, P: Into<Option<&'a str>>, Q: Into<Option<&'b Deployment>>>(&self, osname: P, new_deployment: &Deployment, merge_deployment: Q, flags: SysrootSimpleWriteDeploymentFlags, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        let osname = osname.into();
+        let osname = osname.to_glib_none();
+        let merge_deployment = merge_deployment.into();
+        let merge_deployment = merge_deployment.to_glib_none();
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_simple_write_deployment(self.to_glib_none().0, osname.0, new_deployment.to_glib_none().0, merge_deployment.0, flags.to_glib(), cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn try_lock(&self) -> Result<bool, Error> {
+        unsafe {
+            let mut out_acquired = mem::uninitialized();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_try_lock(self.to_glib_none().0, &mut out_acquired, &mut error);
+            if error.is_null() { Ok(from_glib(out_acquired)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn unload(&self) {
+        unsafe {
+            ffi::ostree_sysroot_unload(self.to_glib_none().0);
+        }
+    }
+
+    pub fn unlock(&self) {
+        unsafe {
+            ffi::ostree_sysroot_unlock(self.to_glib_none().0);
+        }
+    }
+
+    pub fn write_origin_file(&self, deployment: &Deployment, new_origin: Option<&glib::KeyFile>, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_write_origin_file(self.to_glib_none().0, deployment.to_glib_none().0, new_origin.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+}
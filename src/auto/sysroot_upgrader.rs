@@ -0,0 +1,118 @@
+// This file was generated by gir (???) from gir-files (???)
+// DO NOT EDIT
+
+use AsyncProgress;
+use Error;
+use Repo;
+use RepoPullFlags;
+use Sysroot;
+use SysrootUpgraderFlags;
+use SysrootUpgraderPullFlags;
+use ffi;
+use gio;
+use glib;
+use glib::translate::*;
+use std::mem;
+use std::ptr;
+
+glib_wrapper! {
+    pub struct SysrootUpgrader(Object<ffi::OstreeSysrootUpgrader>);
+
+    match fn {
+        get_type => || ffi::ostree_sysroot_upgrader_get_type(),
+    }
+}
+
+impl SysrootUpgrader {
+    pub fn new(sysroot: &Sysroot, cancellable: Option<&gio::Cancellable>) -> Result<SysrootUpgrader, Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = ffi::ostree_sysroot_upgrader_new(sysroot.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(ret)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn new_for_os<'a, P: Into<Option<&'a str>>>(sysroot: &Sysroot, osname: P, cancellable: Option<&gio::Cancellable>) -> Result<SysrootUpgrader, Error> {
+        let osname = osname.into();
+        let osname = osname.to_glib_none();
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = ffi::ostree_sysroot_upgrader_new_for_os(sysroot.to_glib_none().0, osname.0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(ret)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn new_for_os_with_flags<'a, P: Into<Option<&'a str>>>(sysroot: &Sysroot, osname: P, flags: SysrootUpgraderFlags, cancellable: Option<&gio::Cancellable>) -> Result<SysrootUpgrader, Error> {
+        let osname = osname.into();
+        let osname = osname.to_glib_none();
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = ffi::ostree_sysroot_upgrader_new_for_os_with_flags(sysroot.to_glib_none().0, osname.0, flags.to_glib(), cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(ret)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn check_timestamps(repo: &Repo, from_rev: &str, to_rev: &str) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_upgrader_check_timestamps(repo.to_glib_none().0, from_rev.to_glib_none().0, to_rev.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn deploy(&self, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_upgrader_deploy(self.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn dup_origin(&self) -> Option<glib::KeyFile> {
+        unsafe {
+            from_glib_full(ffi::ostree_sysroot_upgrader_dup_origin(self.to_glib_none().0))
+        }
+    }
+
+    pub fn get_origin(&self) -> Option<glib::KeyFile> {
+        unsafe {
+            from_glib_none(ffi::ostree_sysroot_upgrader_get_origin(self.to_glib_none().0))
+        }
+    }
+
+    pub fn get_origin_description(&self) -> Option<String> {
+        unsafe {
+            from_glib_full(ffi::ostree_sysroot_upgrader_get_origin_description(self.to_glib_none().0))
+        }
+    }
+
+    pub fn pull<'a, P: Into<Option<&'a AsyncProgress>>>(&self, flags: RepoPullFlags, upgrader_flags: SysrootUpgraderPullFlags, progress: P, cancellable: Option<&gio::Cancellable>) -> Result<bool, Error> {
+        let progress = progress.into();
+        let progress = progress.to_glib_none();
+        unsafe {
+            let mut out_changed = mem::uninitialized();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_upgrader_pull(self.to_glib_none().0, flags.to_glib(), upgrader_flags.to_glib(), progress.0, &mut out_changed, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib(out_changed)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn pull_one_dir<'a, P: Into<Option<&'a AsyncProgress>>>(&self, dir_to_pull: &str, flags: RepoPullFlags, upgrader_flags: SysrootUpgraderPullFlags, progress: P, cancellable: Option<&gio::Cancellable>) -> Result<bool, Error> {
+        let progress = progress.into();
+        let progress = progress.to_glib_none();
+        unsafe {
+            let mut out_changed = mem::uninitialized();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_upgrader_pull_one_dir(self.to_glib_none().0, dir_to_pull.to_glib_none().0, flags.to_glib(), upgrader_flags.to_glib(), progress.0, &mut out_changed, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib(out_changed)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn set_origin(&self, origin: Option<&glib::KeyFile>, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_upgrader_set_origin(self.to_glib_none().0, origin.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use ffi;
+use glib::translate::*;
+
+use BootconfigParser;
+use KernelArgs;
+
+/// The `[options]` key holding the kernel command line.
+const OPTIONS_KEY: &str = "options";
+
+impl BootconfigParser {
+    /// Returns an independent copy of the parsed entry.
+    pub fn clone_parser(&self) -> BootconfigParser {
+        unsafe {
+            from_glib_full(ffi::ostree_bootconfig_parser_clone(self.to_glib_none().0))
+        }
+    }
+
+    /// The kernel arguments of the `options` line.
+    pub fn options(&self) -> Option<KernelArgs> {
+        self.get(OPTIONS_KEY).map(|options| KernelArgs::parse(&options))
+    }
+
+    /// Replaces the `options` line with `args`.
+    pub fn set_options(&self, args: &KernelArgs) {
+        self.set(OPTIONS_KEY, &args.to_string());
+    }
+}
@@ -0,0 +1,80 @@
+use std::fmt;
+use std::ptr;
+use std::str::FromStr;
+
+use ffi;
+use glib::Error;
+use glib::translate::*;
+use glib_ffi;
+
+const BYTES_LEN: usize = 32;
+
+/// A binary SHA256 object checksum.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checksum([u8; BYTES_LEN]);
+
+impl Checksum {
+    pub fn from_bytes(bytes: [u8; BYTES_LEN]) -> Checksum {
+        Checksum(bytes)
+    }
+
+    /// Parses the 64 character hex form of a checksum.
+    pub fn from_hex(checksum: &str) -> Result<Checksum, Error> {
+        let mut bytes = [0; BYTES_LEN];
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_validate_checksum_string(checksum.to_glib_none().0, &mut error);
+            if !error.is_null() {
+                return Err(from_glib_full(error));
+            }
+            ffi::ostree_checksum_inplace_to_bytes(checksum.to_glib_none().0, bytes.as_mut_ptr());
+        }
+        Ok(Checksum(bytes))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; BYTES_LEN] {
+        &self.0
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+impl FromStr for Checksum {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Checksum, Error> {
+        Checksum::from_hex(s)
+    }
+}
+
+impl fmt::Display for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+impl fmt::Debug for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Checksum({})", self.to_hex())
+    }
+}
+
+#[doc(hidden)]
+impl FromGlibPtrFull<*mut u8> for Checksum {
+    unsafe fn from_glib_full(ptr: *mut u8) -> Checksum {
+        assert!(!ptr.is_null());
+        let mut bytes = [0; BYTES_LEN];
+        ptr::copy_nonoverlapping(ptr, bytes.as_mut_ptr(), BYTES_LEN);
+        glib_ffi::g_free(ptr as *mut _);
+        Checksum(bytes)
+    }
+}
+
+#[doc(hidden)]
+impl FromGlibPtrFull<*mut [u8; BYTES_LEN]> for Checksum {
+    unsafe fn from_glib_full(ptr: *mut [u8; BYTES_LEN]) -> Checksum {
+        from_glib_full(ptr as *mut u8)
+    }
+}
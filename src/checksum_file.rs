@@ -0,0 +1,117 @@
+use std::future::Future;
+use std::io::Read;
+use std::path::Path;
+use std::pin::Pin;
+use std::ptr;
+use std::task::{Context, Poll};
+
+use ffi;
+use gio;
+use gio_ffi;
+use glib::Error;
+use glib::object::IsA;
+use glib::translate::*;
+use glib_ffi;
+use gobject_ffi;
+
+use Checksum;
+use ObjectType;
+use future_utils::{Abandoned, FutureState};
+use read_input_stream;
+use variant_utils;
+
+/// Computes the checksum the file at `path` would have as an object of type
+/// `objtype`, without writing it to a repository.
+pub fn checksum_file<P: AsRef<Path>>(path: P, objtype: ObjectType, cancellable: Option<&gio::Cancellable>) -> Result<Checksum, Error> {
+    let file = gio::File::new_for_path(path);
+    unsafe {
+        let mut out_csum = ptr::null_mut();
+        let mut error = ptr::null_mut();
+        let _ = ffi::ostree_checksum_file(file.to_glib_none().0, objtype.to_glib(), &mut out_csum, cancellable.to_glib_none().0, &mut error);
+        if error.is_null() { Ok(from_glib_full(out_csum)) } else { Err(from_glib_full(error)) }
+    }
+}
+
+/// Like `checksum_file()`, for a file described by `file_info` and `xattrs`
+/// with its contents streamed from `input`. `input` is only needed for
+/// regular files and metadata objects.
+pub fn checksum_file_from_input(file_info: &gio::FileInfo, xattrs: &[(Vec<u8>, Vec<u8>)], input: Option<&gio::InputStream>, objtype: ObjectType, cancellable: Option<&gio::Cancellable>) -> Result<Checksum, Error> {
+    let xattrs = variant_utils::new_xattrs(xattrs);
+    unsafe {
+        let mut out_csum = ptr::null_mut();
+        let mut error = ptr::null_mut();
+        let _ = ffi::ostree_checksum_file_from_input(file_info.to_glib_none().0, xattrs.to_glib_none().0, input.to_glib_none().0, objtype.to_glib(), &mut out_csum, cancellable.to_glib_none().0, &mut error);
+        if error.is_null() { Ok(from_glib_full(out_csum)) } else { Err(from_glib_full(error)) }
+    }
+}
+
+/// Like `checksum_file_from_input()`, reading the contents from `input` as
+/// they are needed.
+pub fn checksum_file_from_read<R: Read>(file_info: &gio::FileInfo, xattrs: &[(Vec<u8>, Vec<u8>)], input: R, objtype: ObjectType, cancellable: Option<&gio::Cancellable>) -> Result<Checksum, Error> {
+    read_input_stream::with_stream(input, |stream| checksum_file_from_input(file_info, xattrs, Some(stream), objtype, cancellable))
+}
+
+/// Computes the checksum of `file` in a worker thread, invoking `callback`
+/// from the thread-default main context when done.
+pub fn checksum_file_async<P: IsA<gio::File>, F: FnOnce(Result<Checksum, Error>) + 'static>(file: &P, objtype: ObjectType, io_priority: i32, cancellable: Option<&gio::Cancellable>, callback: F) {
+    let user_data: Box<Box<F>> = Box::new(Box::new(callback));
+    unsafe {
+        ffi::ostree_checksum_file_async(file.to_glib_none().0, objtype.to_glib(), io_priority, cancellable.to_glib_none().0, Some(checksum_file_async_trampoline::<F>), Box::into_raw(user_data) as glib_ffi::gpointer);
+    }
+}
+
+/// Returns a future resolving to the checksum of the file at `path`, for use
+/// with a GLib main context based executor.
+pub fn checksum_file_future<P: AsRef<Path>>(path: P, objtype: ObjectType, io_priority: i32) -> ChecksumFileFuture {
+    ChecksumFileFuture {
+        file: gio::File::new_for_path(path),
+        objtype,
+        io_priority,
+        cancellable: gio::Cancellable::new(),
+        state: FutureState::new(),
+    }
+}
+
+/// Future returned by `checksum_file_future()`.
+///
+/// Like `LockFuture`, it only makes progress while the thread-default main
+/// context is iterated. Dropping it before completion cancels the operation.
+pub struct ChecksumFileFuture {
+    file: gio::File,
+    objtype: ObjectType,
+    io_priority: i32,
+    cancellable: gio::Cancellable,
+    state: FutureState<Result<Checksum, Error>>,
+}
+
+impl Future for ChecksumFileFuture {
+    type Output = Result<Checksum, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Checksum, Error>> {
+        let this = self.get_mut();
+        this.state.poll(cx, || {
+            let state = this.state.clone();
+            checksum_file_async(&this.file, this.objtype, this.io_priority, Some(&this.cancellable), move |result| {
+                let _ = state.complete(result);
+            });
+        })
+    }
+}
+
+impl Drop for ChecksumFileFuture {
+    fn drop(&mut self) {
+        if let Abandoned::Running = self.state.abandon() {
+            self.cancellable.cancel();
+        }
+    }
+}
+
+unsafe extern "C" fn checksum_file_async_trampoline<F: FnOnce(Result<Checksum, Error>) + 'static>(source_object: *mut gobject_ffi::GObject, res: *mut gio_ffi::GAsyncResult, user_data: glib_ffi::gpointer) {
+    callback_guard!();
+    let callback: Box<Box<F>> = Box::from_raw(user_data as *mut _);
+    let mut out_csum = ptr::null_mut();
+    let mut error = ptr::null_mut();
+    let _ = ffi::ostree_checksum_file_async_finish(source_object as *mut _, res, &mut out_csum, &mut error);
+    let result = if error.is_null() { Ok(from_glib_full(out_csum)) } else { Err(from_glib_full(error)) };
+    callback(result);
+}
@@ -0,0 +1,143 @@
+use std::io::{self, Read};
+use std::ptr;
+
+use ffi;
+use gio;
+use gio_ffi;
+use glib::object::IsA;
+use glib::translate::*;
+use glib_ffi;
+use gobject_ffi;
+
+use io_utils::to_io_error;
+use Checksum;
+use ChecksumInputStream;
+
+const CHECKSUM_KEY: &[u8] = b"ostree-rs-checksum\0";
+
+/// A running SHA256 checksum.
+struct Sha256(*mut glib_ffi::GChecksum);
+
+impl Sha256 {
+    fn new() -> Sha256 {
+        unsafe { Sha256(glib_ffi::g_checksum_new(glib_ffi::G_CHECKSUM_SHA256)) }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        unsafe {
+            glib_ffi::g_checksum_update(self.0, data.as_ptr() as *mut u8, data.len() as isize);
+        }
+    }
+
+    /// The checksum of everything so far; more data can still be added.
+    fn digest(&self) -> Checksum {
+        unsafe { digest(self.0) }
+    }
+}
+
+impl Drop for Sha256 {
+    fn drop(&mut self) {
+        unsafe {
+            glib_ffi::g_checksum_free(self.0);
+        }
+    }
+}
+
+unsafe fn digest(checksum: *mut glib_ffi::GChecksum) -> Checksum {
+    // g_checksum_get_digest() closes the checksum, so work on a copy
+    let copy = glib_ffi::g_checksum_copy(checksum);
+    let mut bytes = [0; 32];
+    let mut len = bytes.len();
+    glib_ffi::g_checksum_get_digest(copy, bytes.as_mut_ptr(), &mut len);
+    glib_ffi::g_checksum_free(copy);
+    Checksum::from_bytes(bytes)
+}
+
+unsafe extern "C" fn free_checksum(checksum: glib_ffi::gpointer) {
+    glib_ffi::g_checksum_free(checksum as *mut glib_ffi::GChecksum);
+}
+
+impl ChecksumInputStream {
+    /// Wraps `stream`, computing the SHA256 checksum of everything read
+    /// through the returned stream.
+    pub fn new<P: IsA<gio::InputStream>>(stream: &P) -> ChecksumInputStream {
+        unsafe {
+            let checksum = glib_ffi::g_checksum_new(glib_ffi::G_CHECKSUM_SHA256);
+            let ret = ffi::ostree_checksum_input_stream_new(stream.to_glib_none().0, checksum);
+            // The stream doesn't own the checksum, so tie it to its lifetime
+            gobject_ffi::g_object_set_data_full(ret as *mut gobject_ffi::GObject, CHECKSUM_KEY.as_ptr() as *const _,
+                                                checksum as glib_ffi::gpointer, Some(free_checksum));
+            from_glib_full(ret)
+        }
+    }
+
+    /// The checksum of the data read so far.
+    ///
+    /// `None` for streams not created by `new()`, e.g. ones handed out by
+    /// libostree, whose checksum isn't reachable.
+    pub fn checksum(&self) -> Option<Checksum> {
+        unsafe {
+            let ptr: *mut ffi::OstreeChecksumInputStream = self.to_glib_none().0;
+            let checksum = gobject_ffi::g_object_get_data(ptr as *mut gobject_ffi::GObject, CHECKSUM_KEY.as_ptr() as *const _);
+            if checksum.is_null() {
+                None
+            } else {
+                Some(digest(checksum as *mut glib_ffi::GChecksum))
+            }
+        }
+    }
+}
+
+impl Read for ChecksumInputStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let stream: *mut gio_ffi::GInputStream = self.to_glib_none().0;
+            let ret = gio_ffi::g_input_stream_read(stream, buf.as_mut_ptr(), buf.len(), ptr::null_mut(), &mut error);
+            if error.is_null() {
+                Ok(ret as usize)
+            } else {
+                Err(to_io_error(&from_glib_full(error)))
+            }
+        }
+    }
+}
+
+/// Computes the SHA256 checksum of everything read through it, like
+/// `ChecksumInputStream` for any `Read`.
+pub struct ChecksumReader<R> {
+    inner: R,
+    checksum: Sha256,
+}
+
+impl<R: Read> ChecksumReader<R> {
+    pub fn new(inner: R) -> ChecksumReader<R> {
+        ChecksumReader {
+            inner,
+            checksum: Sha256::new(),
+        }
+    }
+
+    /// The checksum of the data read so far.
+    pub fn checksum(&self) -> Checksum {
+        self.checksum.digest()
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the reader and the checksum of the data read through it.
+    pub fn into_inner(self) -> (R, Checksum) {
+        let checksum = self.checksum();
+        (self.inner, checksum)
+    }
+}
+
+impl<R: Read> Read for ChecksumReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.checksum.update(&buf[..n]);
+        Ok(n)
+    }
+}
@@ -0,0 +1,240 @@
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+use gio;
+use gio::{FileExt, FileInfo, FileType, FILE_QUERY_INFO_NOFOLLOW_SYMLINKS};
+use glib::{Error, FileError};
+use glib::object::Downcast;
+
+use io_utils::to_glib_error;
+use Diff;
+use DiffItem;
+use DIFF_FLAGS_NONE;
+use Repo;
+use RepoFile;
+use diff_dirs;
+
+const QUERY_ATTRIBUTES: &str = "standard::name,standard::type,standard::size,standard::symlink-target,unix::mode,unix::uid,unix::gid";
+const COMPARE_BLOCK_SIZE: usize = 64 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PathChangeKind {
+    /// Only in the new commit.
+    Added,
+    /// Only in the old commit.
+    Removed,
+    /// The file content changed, and maybe its metadata too.
+    ContentModified,
+    /// Only the mode, ownership or extended attributes changed.
+    MetadataModified,
+}
+
+/// A path that differs between two commits.
+///
+/// The checksums are the content checksums of files and the metadata
+/// checksums of directories. Sizes are not set for directories.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PathChange {
+    /// Absolute path in the commit, e.g. `/usr/bin/bash`.
+    pub path: PathBuf,
+    pub kind: PathChangeKind,
+    pub old_checksum: Option<String>,
+    pub new_checksum: Option<String>,
+    pub old_size: Option<u64>,
+    pub new_size: Option<u64>,
+}
+
+/// The result of `Repo::diff_commits()`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommitDiff {
+    /// Changed paths, sorted. Added and removed directories are followed by
+    /// all their contents. A path that changed type, e.g. from a file to a
+    /// directory, is removed and then added again.
+    pub changes: Vec<PathChange>,
+}
+
+/// Totals over the changes of a `CommitDiff`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DiffSummary {
+    pub added: usize,
+    pub removed: usize,
+    pub content_modified: usize,
+    pub metadata_modified: usize,
+    /// Total size of added files and of the new versions of modified files.
+    pub added_size: u64,
+    /// Total size of removed files and of the old versions of modified files.
+    pub removed_size: u64,
+}
+
+impl CommitDiff {
+    pub fn summary(&self) -> DiffSummary {
+        let mut summary = DiffSummary::default();
+        for change in &self.changes {
+            match change.kind {
+                PathChangeKind::Added => summary.added += 1,
+                PathChangeKind::Removed => summary.removed += 1,
+                PathChangeKind::ContentModified => summary.content_modified += 1,
+                PathChangeKind::MetadataModified => {
+                    summary.metadata_modified += 1;
+                    continue;
+                }
+            }
+            summary.added_size += change.new_size.unwrap_or(0);
+            summary.removed_size += change.old_size.unwrap_or(0);
+        }
+        summary
+    }
+}
+
+impl DiffSummary {
+    /// How much bigger the new commit is, in bytes.
+    pub fn size_delta(&self) -> i64 {
+        self.added_size as i64 - self.removed_size as i64
+    }
+}
+
+impl Repo {
+    /// Compares the trees of the commits `from` and `to`, which can be refs
+    /// or checksums, without checking them out.
+    pub fn diff_commits(&self, from: &str, to: &str, cancellable: Option<&gio::Cancellable>) -> Result<CommitDiff, Error> {
+        let (from_root, _) = self.read_commit(from, cancellable)?;
+        let (to_root, _) = self.read_commit(to, cancellable)?;
+        let Diff { modified, removed, added } = diff_dirs(DIFF_FLAGS_NONE, &from_root, &to_root, cancellable)?;
+
+        let mut changes = Vec::new();
+        for item in &modified {
+            if item.src_info().get_file_type() == item.target_info().get_file_type() {
+                changes.push(modified_change(item, cancellable)?);
+            } else {
+                // A file replaced by a directory or the other way around; the
+                // old and new subtrees have nothing in common
+                walk(repo_file(item.src())?, PathChangeKind::Removed, &mut changes, cancellable)?;
+                walk(repo_file(item.target())?, PathChangeKind::Added, &mut changes, cancellable)?;
+            }
+        }
+        for file in removed {
+            walk(repo_file(file)?, PathChangeKind::Removed, &mut changes, cancellable)?;
+        }
+        for file in added {
+            walk(repo_file(file)?, PathChangeKind::Added, &mut changes, cancellable)?;
+        }
+        // Stable, so a removed path stays before the added one replacing it
+        changes.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(CommitDiff { changes })
+    }
+}
+
+fn repo_file(file: gio::File) -> Result<RepoFile, Error> {
+    file.downcast().map_err(|_| Error::new(FileError::Inval, "Not a file of a repository tree"))
+}
+
+fn checksum(file: &RepoFile, info: &FileInfo) -> Result<Option<String>, Error> {
+    if info.get_file_type() == FileType::Directory {
+        file.ensure_resolved()?;
+        Ok(file.tree_get_metadata_checksum())
+    } else {
+        Ok(file.get_checksum())
+    }
+}
+
+fn size(info: &FileInfo) -> Option<u64> {
+    if info.get_file_type() == FileType::Directory {
+        None
+    } else {
+        Some(info.get_size() as u64)
+    }
+}
+
+/// Adds `file` as added or removed, and everything below it.
+fn walk(file: RepoFile, kind: PathChangeKind, changes: &mut Vec<PathChange>, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+    let info = file.query_info(QUERY_ATTRIBUTES, FILE_QUERY_INFO_NOFOLLOW_SYMLINKS, cancellable)?;
+    let (checksum, size) = (checksum(&file, &info)?, size(&info));
+    changes.push(PathChange {
+        path: file.get_path().unwrap_or_default(),
+        kind,
+        old_checksum: if kind == PathChangeKind::Removed { checksum.clone() } else { None },
+        new_checksum: if kind == PathChangeKind::Added { checksum } else { None },
+        old_size: if kind == PathChangeKind::Removed { size } else { None },
+        new_size: if kind == PathChangeKind::Added { size } else { None },
+    });
+    if info.get_file_type() != FileType::Directory {
+        return Ok(());
+    }
+    let children = file.enumerate_children(QUERY_ATTRIBUTES, FILE_QUERY_INFO_NOFOLLOW_SYMLINKS, cancellable)?;
+    while let Some(child) = children.next_file(cancellable)? {
+        if let Some(child) = child.get_name().and_then(|name| file.get_child(name)) {
+            walk(repo_file(child)?, kind, changes, cancellable)?;
+        }
+    }
+    Ok(())
+}
+
+/// Classifies a change of a file that kept its type.
+fn modified_change(item: &DiffItem, cancellable: Option<&gio::Cancellable>) -> Result<PathChange, Error> {
+    let (src, target) = (repo_file(item.src())?, repo_file(item.target())?);
+    let (src_info, target_info) = (item.src_info(), item.target_info());
+    // The checksums cover both content and metadata, so look at the metadata
+    // to tell which one changed
+    let kind = if src_info.get_file_type() == FileType::Directory {
+        PathChangeKind::MetadataModified
+    } else if metadata_equal(&src, &src_info, &target, &target_info, cancellable)? {
+        PathChangeKind::ContentModified
+    } else if content_equal(&src, &src_info, &target, &target_info, cancellable)? {
+        PathChangeKind::MetadataModified
+    } else {
+        PathChangeKind::ContentModified
+    };
+    Ok(PathChange {
+        path: target.get_path().unwrap_or_default(),
+        kind,
+        old_checksum: item.src_checksum(),
+        new_checksum: item.target_checksum(),
+        old_size: size(&src_info),
+        new_size: size(&target_info),
+    })
+}
+
+fn metadata_equal(a: &RepoFile, a_info: &FileInfo, b: &RepoFile, b_info: &FileInfo, cancellable: Option<&gio::Cancellable>) -> Result<bool, Error> {
+    for attribute in &["unix::mode", "unix::uid", "unix::gid"] {
+        if a_info.get_attribute_uint32(attribute) != b_info.get_attribute_uint32(attribute) {
+            return Ok(false);
+        }
+    }
+    Ok(a.get_xattrs(cancellable)? == b.get_xattrs(cancellable)?)
+}
+
+fn content_equal(a: &RepoFile, a_info: &FileInfo, b: &RepoFile, b_info: &FileInfo, cancellable: Option<&gio::Cancellable>) -> Result<bool, Error> {
+    if a_info.get_file_type() != b_info.get_file_type() || a_info.get_size() != b_info.get_size() {
+        return Ok(false);
+    }
+    if a_info.get_file_type() == FileType::SymbolicLink {
+        return Ok(a_info.get_symlink_target() == b_info.get_symlink_target());
+    }
+    let (mut a, mut b) = (a.read(cancellable)?, b.read(cancellable)?);
+    let (mut a_buf, mut b_buf) = (vec![0; COMPARE_BLOCK_SIZE], vec![0; COMPARE_BLOCK_SIZE]);
+    loop {
+        let a_len = read_block(&mut a, &mut a_buf).map_err(|err| to_glib_error(&err))?;
+        let b_len = read_block(&mut b, &mut b_buf).map_err(|err| to_glib_error(&err))?;
+        if a_buf[..a_len] != b_buf[..b_len] {
+            return Ok(false);
+        }
+        if a_len == 0 {
+            return Ok(true);
+        }
+    }
+}
+
+/// Fills `buf` as far as the input allows, returning the number of bytes
+/// read.
+fn read_block<R: Read>(input: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut len = 0;
+    while len < buf.len() {
+        match input.read(&mut buf[len..]) {
+            Ok(0) => break,
+            Ok(n) => len += n,
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(len)
+}
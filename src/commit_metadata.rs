@@ -0,0 +1,146 @@
+use std::collections::BTreeMap;
+use std::ops::{Deref, DerefMut};
+
+use glib::{Error, FileError, ToVariant, Variant, VariantTy};
+use glib::translate::*;
+use glib_ffi;
+
+use variant_utils;
+
+/// Refs a commit may be deployed from (`as`).
+pub const COMMIT_META_KEY_REF_BINDING: &str = "ostree.ref-binding";
+/// Human readable version of the commit (`s`).
+pub const COMMIT_META_KEY_VERSION: &str = "version";
+/// Reason the commit's branch is no longer updated (`s`).
+pub const COMMIT_META_KEY_ENDOFLIFE: &str = "ostree.endoflife";
+/// Ref that clients should rebase to once the branch is end-of-life (`s`).
+pub const COMMIT_META_KEY_ENDOFLIFE_REBASE: &str = "ostree.endoflife-rebase";
+
+/// The `a{sv}` dictionary used for commit and detached commit metadata.
+///
+/// Dereferences to the underlying `BTreeMap`, so arbitrary keys can be
+/// accessed directly; the well-known OSTree keys have typed accessors.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommitMetadata(BTreeMap<String, Variant>);
+
+impl CommitMetadata {
+    pub fn new() -> CommitMetadata {
+        CommitMetadata::default()
+    }
+
+    /// Parses an `a{sv}` variant.
+    pub fn from_variant(variant: &Variant) -> Result<CommitMetadata, Error> {
+        if variant.type_().to_str() != "a{sv}" {
+            return Err(Error::new(FileError::Inval,
+                                  &format!("Invalid metadata type '{}', expected 'a{{sv}}'",
+                                           variant.type_().to_str())));
+        }
+
+        let mut map = BTreeMap::new();
+        for i in 0..variant_utils::n_children(variant) {
+            let entry = variant_utils::child(variant, i);
+            let key = variant_utils::child_str(&entry, 0);
+            let value = unsafe {
+                from_glib_full(glib_ffi::g_variant_get_variant(variant_utils::child(&entry, 1).to_glib_none().0))
+            };
+            map.insert(key, value);
+        }
+        Ok(CommitMetadata(map))
+    }
+
+    /// Serializes the metadata as an `a{sv}` variant.
+    pub fn to_variant(&self) -> Variant {
+        let type_ = VariantTy::new("a{sv}").unwrap();
+        unsafe {
+            let builder = glib_ffi::g_variant_builder_new(type_.as_ptr());
+            for (key, value) in &self.0 {
+                let entry = glib_ffi::g_variant_new_dict_entry(
+                    glib_ffi::g_variant_new_string(key.to_glib_none().0),
+                    glib_ffi::g_variant_new_variant(value.to_glib_none().0));
+                glib_ffi::g_variant_builder_add_value(builder, entry);
+            }
+            let ret = glib_ffi::g_variant_builder_end(builder);
+            glib_ffi::g_variant_builder_unref(builder);
+            from_glib_none(ret)
+        }
+    }
+
+    pub fn into_inner(self) -> BTreeMap<String, Variant> {
+        self.0
+    }
+
+    pub fn ref_binding(&self) -> Option<Vec<String>> {
+        let value = match self.0.get(COMMIT_META_KEY_REF_BINDING) {
+            Some(value) if value.type_().to_str() == "as" => value,
+            _ => return None,
+        };
+        unsafe {
+            let mut len = 0;
+            let strv = glib_ffi::g_variant_get_strv(value.to_glib_none().0, &mut len);
+            let ret = FromGlibPtrContainer::from_glib_none_num(strv as *const *const _, len);
+            glib_ffi::g_free(strv as *mut _);
+            Some(ret)
+        }
+    }
+
+    pub fn set_ref_binding(&mut self, refs: &[&str]) {
+        let value = unsafe {
+            from_glib_none(glib_ffi::g_variant_new_strv(refs.to_glib_none().0, -1))
+        };
+        self.0.insert(COMMIT_META_KEY_REF_BINDING.to_owned(), value);
+    }
+
+    pub fn version(&self) -> Option<&str> {
+        self.get_str(COMMIT_META_KEY_VERSION)
+    }
+
+    pub fn set_version(&mut self, version: &str) {
+        self.0.insert(COMMIT_META_KEY_VERSION.to_owned(), version.to_variant());
+    }
+
+    pub fn endoflife(&self) -> Option<&str> {
+        self.get_str(COMMIT_META_KEY_ENDOFLIFE)
+    }
+
+    pub fn set_endoflife(&mut self, reason: &str) {
+        self.0.insert(COMMIT_META_KEY_ENDOFLIFE.to_owned(), reason.to_variant());
+    }
+
+    pub fn endoflife_rebase(&self) -> Option<&str> {
+        self.get_str(COMMIT_META_KEY_ENDOFLIFE_REBASE)
+    }
+
+    pub fn set_endoflife_rebase(&mut self, ref_: &str) {
+        self.0.insert(COMMIT_META_KEY_ENDOFLIFE_REBASE.to_owned(), ref_.to_variant());
+    }
+
+    fn get_str(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.get_str())
+    }
+}
+
+impl Deref for CommitMetadata {
+    type Target = BTreeMap<String, Variant>;
+
+    fn deref(&self) -> &BTreeMap<String, Variant> {
+        &self.0
+    }
+}
+
+impl DerefMut for CommitMetadata {
+    fn deref_mut(&mut self) -> &mut BTreeMap<String, Variant> {
+        &mut self.0
+    }
+}
+
+impl From<BTreeMap<String, Variant>> for CommitMetadata {
+    fn from(map: BTreeMap<String, Variant>) -> CommitMetadata {
+        CommitMetadata(map)
+    }
+}
+
+impl From<CommitMetadata> for BTreeMap<String, Variant> {
+    fn from(metadata: CommitMetadata) -> BTreeMap<String, Variant> {
+        metadata.0
+    }
+}
@@ -0,0 +1,268 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+use ffi;
+use glib;
+use glib::object::{GObject, IsA, Object, ObjectRef};
+use glib::translate::*;
+use glib::types::{StaticType, Type};
+use glib::wrapper::{UnsafeFrom, Wrapper};
+use glib_ffi;
+
+use BootconfigParser;
+use DeploymentUnlockedState;
+
+/// A deployment of a commit in a sysroot.
+///
+/// Unlike other object wrappers, `Clone`, `PartialEq` and `Hash` work on the
+/// deployment rather than on the object: `clone()` makes a copy with its own
+/// origin and boot configuration, and two deployments are equal if they have
+/// the same OS name, checksum and deployment serial.
+pub struct Deployment(ObjectRef, PhantomData<ffi::OstreeDeployment>);
+
+impl Clone for Deployment {
+    fn clone(&self) -> Deployment {
+        unsafe {
+            from_glib_full(ffi::ostree_deployment_clone(self.to_glib_none().0))
+        }
+    }
+}
+
+impl PartialEq for Deployment {
+    fn eq(&self, other: &Deployment) -> bool {
+        unsafe {
+            let a: *mut ffi::OstreeDeployment = self.to_glib_none().0;
+            let b: *mut ffi::OstreeDeployment = other.to_glib_none().0;
+            from_glib(ffi::ostree_deployment_equal(a as glib_ffi::gconstpointer, b as glib_ffi::gconstpointer))
+        }
+    }
+}
+
+impl Eq for Deployment { }
+
+impl Hash for Deployment {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        unsafe {
+            let ptr: *mut ffi::OstreeDeployment = self.to_glib_none().0;
+            state.write_u32(ffi::ostree_deployment_hash(ptr as glib_ffi::gconstpointer));
+        }
+    }
+}
+
+impl fmt::Debug for Deployment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Deployment")
+            .field("osname", &self.get_osname())
+            .field("csum", &self.get_csum())
+            .field("deployserial", &self.get_deployserial())
+            .finish()
+    }
+}
+
+#[doc(hidden)]
+impl From<Deployment> for ObjectRef {
+    fn from(deployment: Deployment) -> ObjectRef {
+        deployment.0
+    }
+}
+
+#[doc(hidden)]
+impl UnsafeFrom<ObjectRef> for Deployment {
+    unsafe fn from(t: ObjectRef) -> Self {
+        Deployment(t, PhantomData)
+    }
+}
+
+#[doc(hidden)]
+impl GlibPtrDefault for Deployment {
+    type GlibType = *mut ffi::OstreeDeployment;
+}
+
+#[doc(hidden)]
+impl Wrapper for Deployment {
+    type GlibType = ffi::OstreeDeployment;
+}
+
+#[doc(hidden)]
+impl<'a> ToGlibPtr<'a, *const ffi::OstreeDeployment> for Deployment {
+    type Storage = <ObjectRef as ToGlibPtr<'a, *mut GObject>>::Storage;
+
+    #[inline]
+    fn to_glib_none(&'a self) -> Stash<'a, *const ffi::OstreeDeployment, Self> {
+        let stash = self.0.to_glib_none();
+        Stash(stash.0 as *const _, stash.1)
+    }
+
+    #[inline]
+    fn to_glib_full(&self) -> *const ffi::OstreeDeployment {
+        self.0.to_glib_full() as *const _
+    }
+}
+
+#[doc(hidden)]
+impl<'a> ToGlibPtr<'a, *mut ffi::OstreeDeployment> for Deployment {
+    type Storage = <ObjectRef as ToGlibPtr<'a, *mut GObject>>::Storage;
+
+    #[inline]
+    fn to_glib_none(&'a self) -> Stash<'a, *mut ffi::OstreeDeployment, Self> {
+        let stash = self.0.to_glib_none();
+        Stash(stash.0 as *mut _, stash.1)
+    }
+
+    #[inline]
+    fn to_glib_full(&self) -> *mut ffi::OstreeDeployment {
+        self.0.to_glib_full() as *mut _
+    }
+}
+
+#[doc(hidden)]
+impl<'a> ToGlibPtr<'a, *mut GObject> for Deployment {
+    type Storage = <ObjectRef as ToGlibPtr<'a, *mut GObject>>::Storage;
+
+    #[inline]
+    fn to_glib_none(&'a self) -> Stash<'a, *mut GObject, Self> {
+        let stash = self.0.to_glib_none();
+        Stash(stash.0 as *mut _, stash.1)
+    }
+
+    #[inline]
+    fn to_glib_full(&self) -> *mut GObject {
+        self.0.to_glib_full() as *mut _
+    }
+}
+
+#[doc(hidden)]
+impl FromGlibPtrNone<*mut ffi::OstreeDeployment> for Deployment {
+    #[inline]
+    unsafe fn from_glib_none(ptr: *mut ffi::OstreeDeployment) -> Self {
+        Deployment(from_glib_none(ptr as *mut _), PhantomData)
+    }
+}
+
+#[doc(hidden)]
+impl FromGlibPtrFull<*mut ffi::OstreeDeployment> for Deployment {
+    #[inline]
+    unsafe fn from_glib_full(ptr: *mut ffi::OstreeDeployment) -> Self {
+        Deployment(from_glib_full(ptr as *mut _), PhantomData)
+    }
+}
+
+#[doc(hidden)]
+impl FromGlibPtrBorrow<*mut ffi::OstreeDeployment> for Deployment {
+    #[inline]
+    unsafe fn from_glib_borrow(ptr: *mut ffi::OstreeDeployment) -> Self {
+        Deployment(from_glib_borrow(ptr as *mut _), PhantomData)
+    }
+}
+
+impl StaticType for Deployment {
+    fn static_type() -> Type {
+        unsafe { from_glib(ffi::ostree_deployment_get_type()) }
+    }
+}
+
+impl IsA<Object> for Deployment { }
+
+impl Deployment {
+    pub fn new<'a, P: Into<Option<&'a str>>>(index: i32, osname: &str, csum: &str, deployserial: i32, bootcsum: P, bootserial: i32) -> Deployment {
+        let bootcsum = bootcsum.into();
+        let bootcsum = bootcsum.to_glib_none();
+        unsafe {
+            from_glib_full(ffi::ostree_deployment_new(index, osname.to_glib_none().0, csum.to_glib_none().0, deployserial, bootcsum.0, bootserial))
+        }
+    }
+
+    pub fn get_bootconfig(&self) -> Option<BootconfigParser> {
+        unsafe {
+            from_glib_none(ffi::ostree_deployment_get_bootconfig(self.to_glib_none().0))
+        }
+    }
+
+    pub fn get_bootcsum(&self) -> Option<String> {
+        unsafe {
+            from_glib_none(ffi::ostree_deployment_get_bootcsum(self.to_glib_none().0))
+        }
+    }
+
+    pub fn get_bootserial(&self) -> i32 {
+        unsafe {
+            ffi::ostree_deployment_get_bootserial(self.to_glib_none().0)
+        }
+    }
+
+    pub fn get_csum(&self) -> Option<String> {
+        unsafe {
+            from_glib_none(ffi::ostree_deployment_get_csum(self.to_glib_none().0))
+        }
+    }
+
+    pub fn get_deployserial(&self) -> i32 {
+        unsafe {
+            ffi::ostree_deployment_get_deployserial(self.to_glib_none().0)
+        }
+    }
+
+    pub fn get_index(&self) -> i32 {
+        unsafe {
+            ffi::ostree_deployment_get_index(self.to_glib_none().0)
+        }
+    }
+
+    pub fn get_origin(&self) -> Option<glib::KeyFile> {
+        unsafe {
+            from_glib_none(ffi::ostree_deployment_get_origin(self.to_glib_none().0))
+        }
+    }
+
+    pub fn get_origin_relpath(&self) -> Option<String> {
+        unsafe {
+            from_glib_full(ffi::ostree_deployment_get_origin_relpath(self.to_glib_none().0))
+        }
+    }
+
+    pub fn get_osname(&self) -> Option<String> {
+        unsafe {
+            from_glib_none(ffi::ostree_deployment_get_osname(self.to_glib_none().0))
+        }
+    }
+
+    pub fn get_unlocked(&self) -> DeploymentUnlockedState {
+        unsafe {
+            from_glib(ffi::ostree_deployment_get_unlocked(self.to_glib_none().0))
+        }
+    }
+
+    pub fn set_bootconfig(&self, bootconfig: &BootconfigParser) {
+        unsafe {
+            ffi::ostree_deployment_set_bootconfig(self.to_glib_none().0, bootconfig.to_glib_none().0);
+        }
+    }
+
+    pub fn set_bootserial(&self, index: i32) {
+        unsafe {
+            ffi::ostree_deployment_set_bootserial(self.to_glib_none().0, index);
+        }
+    }
+
+    pub fn set_index(&self, index: i32) {
+        unsafe {
+            ffi::ostree_deployment_set_index(self.to_glib_none().0, index);
+        }
+    }
+
+    pub fn set_origin(&self, origin: &glib::KeyFile) {
+        unsafe {
+            ffi::ostree_deployment_set_origin(self.to_glib_none().0, origin.to_glib_none().0);
+        }
+    }
+}
+
+impl fmt::Display for DeploymentUnlockedState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name: String = unsafe {
+            from_glib_none(ffi::ostree_deployment_unlocked_state_to_string(self.to_glib()))
+        };
+        f.write_str(&name)
+    }
+}
@@ -0,0 +1,95 @@
+use std::ptr;
+
+use ffi;
+use gio;
+use glib::Error;
+use glib::object::IsA;
+use glib::translate::*;
+use gio_ffi;
+use glib_ffi;
+
+use DiffFlags;
+use DiffItem;
+use ptr_array;
+
+/// The differences between two directory trees, as found by `diff_dirs()`.
+#[derive(Clone, Default)]
+pub struct Diff {
+    /// Files present in both trees with different content or metadata.
+    pub modified: Vec<DiffItem>,
+    /// Files only in the first tree.
+    pub removed: Vec<gio::File>,
+    /// Files only in the second tree.
+    pub added: Vec<gio::File>,
+}
+
+/// Compares the trees `a` and `b`, which can be `RepoFile` roots of commits
+/// as well as local directories.
+pub fn diff_dirs<P: IsA<gio::File>, Q: IsA<gio::File>>(flags: DiffFlags, a: &P, b: &Q, cancellable: Option<&gio::Cancellable>) -> Result<Diff, Error> {
+    unsafe {
+        let modified = glib_ffi::g_ptr_array_new();
+        let removed = glib_ffi::g_ptr_array_new();
+        let added = glib_ffi::g_ptr_array_new();
+        let mut error = ptr::null_mut();
+        let _ = ffi::ostree_diff_dirs(flags.to_glib(), a.to_glib_none().0, b.to_glib_none().0, modified, removed, added, cancellable.to_glib_none().0, &mut error);
+        // The arrays have no free function, so the references move to the
+        // wrappers even if the diff failed halfway
+        let diff = Diff {
+            modified: ptr_array::take(modified, |item| from_glib_full(item as *mut ffi::OstreeDiffItem)),
+            removed: ptr_array::take(removed, |file| from_glib_full(file as *mut gio_ffi::GFile)),
+            added: ptr_array::take(added, |file| from_glib_full(file as *mut gio_ffi::GFile)),
+        };
+        if error.is_null() { Ok(diff) } else { Err(from_glib_full(error)) }
+    }
+}
+
+impl Diff {
+    pub fn is_empty(&self) -> bool {
+        self.modified.is_empty() && self.removed.is_empty() && self.added.is_empty()
+    }
+
+    /// Prints the differences to stdout, with paths relative to the
+    /// compared trees `a` and `b`.
+    pub fn print<P: IsA<gio::File>, Q: IsA<gio::File>>(&self, a: &P, b: &Q) {
+        unsafe {
+            let modified = ptr_array::from_slice(&self.modified);
+            let removed = ptr_array::from_slice::<_, gio_ffi::GFile>(&self.removed);
+            let added = ptr_array::from_slice::<_, gio_ffi::GFile>(&self.added);
+            ffi::ostree_diff_print(a.to_glib_none().0, b.to_glib_none().0, modified, removed, added);
+            glib_ffi::g_ptr_array_unref(modified);
+            glib_ffi::g_ptr_array_unref(removed);
+            glib_ffi::g_ptr_array_unref(added);
+        }
+    }
+}
+
+impl DiffItem {
+    /// The file in the first tree.
+    pub fn src(&self) -> gio::File {
+        unsafe { from_glib_none((*self.to_glib_none().0).src) }
+    }
+
+    /// The file in the second tree.
+    pub fn target(&self) -> gio::File {
+        unsafe { from_glib_none((*self.to_glib_none().0).target) }
+    }
+
+    pub fn src_info(&self) -> gio::FileInfo {
+        unsafe { from_glib_none((*self.to_glib_none().0).src_info) }
+    }
+
+    pub fn target_info(&self) -> gio::FileInfo {
+        unsafe { from_glib_none((*self.to_glib_none().0).target_info) }
+    }
+
+    /// The checksum of `src()`: its content checksum for files, its dirmeta
+    /// checksum for directories. `None` if the file type changed.
+    pub fn src_checksum(&self) -> Option<String> {
+        unsafe { from_glib_none((*self.to_glib_none().0).src_checksum) }
+    }
+
+    /// The checksum of `target()`, like `src_checksum()`.
+    pub fn target_checksum(&self) -> Option<String> {
+        unsafe { from_glib_none((*self.to_glib_none().0).target_checksum) }
+    }
+}
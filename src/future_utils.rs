@@ -0,0 +1,94 @@
+//! State shared between a future and the GIO callback completing it.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+struct Inner<T> {
+    started: bool,
+    // The result was handed out.
+    done: bool,
+    result: Option<T>,
+    waker: Option<Waker>,
+    // The future went away before the operation finished.
+    abandoned: bool,
+}
+
+/// What was left of an operation when its future was dropped.
+pub enum Abandoned<T> {
+    /// Never started, or its result was already handed out.
+    Idle,
+    /// Still running; the caller should cancel it.
+    Running,
+    /// Finished, but nobody polled for the result.
+    Finished(T),
+}
+
+pub struct FutureState<T>(Rc<RefCell<Inner<T>>>);
+
+impl<T> Clone for FutureState<T> {
+    fn clone(&self) -> FutureState<T> {
+        FutureState(self.0.clone())
+    }
+}
+
+impl<T> FutureState<T> {
+    pub fn new() -> FutureState<T> {
+        FutureState(Rc::new(RefCell::new(Inner {
+            started: false,
+            done: false,
+            result: None,
+            waker: None,
+            abandoned: false,
+        })))
+    }
+
+    /// Returns the result if there is one, otherwise registers the waker of
+    /// `cx` and calls `start` on the first poll.
+    pub fn poll<F: FnOnce()>(&self, cx: &mut Context, start: F) -> Poll<T> {
+        {
+            let mut inner = self.0.borrow_mut();
+            if let Some(result) = inner.result.take() {
+                inner.done = true;
+                return Poll::Ready(result);
+            }
+            inner.waker = Some(cx.waker().clone());
+            if inner.started {
+                return Poll::Pending;
+            }
+            inner.started = true;
+        }
+        start();
+        Poll::Pending
+    }
+
+    /// Stores the result of the operation and wakes the future, or gives
+    /// the result back if the future was dropped.
+    pub fn complete(&self, result: T) -> Result<(), T> {
+        let waker = {
+            let mut inner = self.0.borrow_mut();
+            if inner.abandoned {
+                return Err(result);
+            }
+            inner.result = Some(result);
+            inner.waker.take()
+        };
+        // Woken with the state released, as the executor may poll right away
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        Ok(())
+    }
+
+    /// Called when the future is dropped. A result arriving later is given
+    /// back by `complete()`.
+    pub fn abandon(&self) -> Abandoned<T> {
+        let mut inner = self.0.borrow_mut();
+        inner.abandoned = true;
+        match inner.result.take() {
+            Some(result) => Abandoned::Finished(result),
+            None if inner.started && !inner.done => Abandoned::Running,
+            None => Abandoned::Idle,
+        }
+    }
+}
@@ -0,0 +1,288 @@
+//! GIO types used by the OSTree API.
+//!
+//! The `gio` crate doesn't bind files, streams and cancellables yet, so the
+//! ones OSTree needs are wrapped here. Everything else is re-exported from
+//! the `gio` crate.
+
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::ptr;
+
+use gio_ffi;
+use glib::object::IsA;
+use glib::translate::*;
+use glib_ffi;
+
+use io_utils::to_io_error;
+use read_input_stream;
+
+pub use gio_crate::*;
+
+pub mod prelude {
+    pub use gio_crate::prelude::*;
+    pub use super::FileExt;
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum FileType {
+    Unknown,
+    Regular,
+    Directory,
+    SymbolicLink,
+    Special,
+    Shortcut,
+    Mountable,
+}
+
+#[doc(hidden)]
+impl FromGlib<gio_ffi::GFileType> for FileType {
+    fn from_glib(value: gio_ffi::GFileType) -> Self {
+        match value {
+            gio_ffi::G_FILE_TYPE_UNKNOWN => FileType::Unknown,
+            gio_ffi::G_FILE_TYPE_REGULAR => FileType::Regular,
+            gio_ffi::G_FILE_TYPE_DIRECTORY => FileType::Directory,
+            gio_ffi::G_FILE_TYPE_SYMBOLIC_LINK => FileType::SymbolicLink,
+            gio_ffi::G_FILE_TYPE_SPECIAL => FileType::Special,
+            gio_ffi::G_FILE_TYPE_SHORTCUT => FileType::Shortcut,
+            gio_ffi::G_FILE_TYPE_MOUNTABLE => FileType::Mountable,
+        }
+    }
+}
+
+bitflags! {
+    pub flags FileQueryInfoFlags: u32 {
+        const FILE_QUERY_INFO_NONE = 0,
+        const FILE_QUERY_INFO_NOFOLLOW_SYMLINKS = 1,
+    }
+}
+
+#[doc(hidden)]
+impl ToGlib for FileQueryInfoFlags {
+    type GlibType = gio_ffi::GFileQueryInfoFlags;
+
+    fn to_glib(&self) -> gio_ffi::GFileQueryInfoFlags {
+        gio_ffi::GFileQueryInfoFlags::from_bits_truncate(self.bits())
+    }
+}
+
+glib_wrapper! {
+    pub struct Cancellable(Object<gio_ffi::GCancellable>);
+
+    match fn {
+        get_type => || gio_ffi::g_cancellable_get_type(),
+    }
+}
+
+impl Cancellable {
+    pub fn new() -> Cancellable {
+        unsafe {
+            from_glib_full(gio_ffi::g_cancellable_new())
+        }
+    }
+
+    pub fn cancel(&self) {
+        unsafe {
+            gio_ffi::g_cancellable_cancel(self.to_glib_none().0);
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        unsafe {
+            from_glib(gio_ffi::g_cancellable_is_cancelled(self.to_glib_none().0))
+        }
+    }
+}
+
+impl Default for Cancellable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+glib_wrapper! {
+    pub struct File(Object<gio_ffi::GFile>);
+
+    match fn {
+        get_type => || gio_ffi::g_file_get_type(),
+    }
+}
+
+impl File {
+    pub fn new_for_path<P: AsRef<Path>>(path: P) -> File {
+        unsafe {
+            from_glib_full(gio_ffi::g_file_new_for_path(path.as_ref().to_glib_none().0))
+        }
+    }
+}
+
+pub trait FileExt {
+    fn enumerate_children(&self, attributes: &str, flags: FileQueryInfoFlags, cancellable: Option<&Cancellable>) -> Result<FileEnumerator, Error>;
+
+    fn get_basename(&self) -> Option<PathBuf>;
+
+    fn get_child<P: AsRef<Path>>(&self, name: P) -> Option<File>;
+
+    fn get_path(&self) -> Option<PathBuf>;
+
+    fn query_info(&self, attributes: &str, flags: FileQueryInfoFlags, cancellable: Option<&Cancellable>) -> Result<FileInfo, Error>;
+
+    fn read(&self, cancellable: Option<&Cancellable>) -> Result<InputStream, Error>;
+}
+
+impl<O: IsA<File>> FileExt for O {
+    fn enumerate_children(&self, attributes: &str, flags: FileQueryInfoFlags, cancellable: Option<&Cancellable>) -> Result<FileEnumerator, Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = gio_ffi::g_file_enumerate_children(self.to_glib_none().0, attributes.to_glib_none().0, flags.to_glib(), cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(ret)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    fn get_basename(&self) -> Option<PathBuf> {
+        unsafe {
+            from_glib_full(gio_ffi::g_file_get_basename(self.to_glib_none().0))
+        }
+    }
+
+    fn get_child<P: AsRef<Path>>(&self, name: P) -> Option<File> {
+        unsafe {
+            from_glib_full(gio_ffi::g_file_get_child(self.to_glib_none().0, name.as_ref().to_glib_none().0))
+        }
+    }
+
+    fn get_path(&self) -> Option<PathBuf> {
+        unsafe {
+            from_glib_full(gio_ffi::g_file_get_path(self.to_glib_none().0))
+        }
+    }
+
+    fn query_info(&self, attributes: &str, flags: FileQueryInfoFlags, cancellable: Option<&Cancellable>) -> Result<FileInfo, Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = gio_ffi::g_file_query_info(self.to_glib_none().0, attributes.to_glib_none().0, flags.to_glib(), cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(ret)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    fn read(&self, cancellable: Option<&Cancellable>) -> Result<InputStream, Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = gio_ffi::g_file_read(self.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(ret as *mut gio_ffi::GInputStream)) } else { Err(from_glib_full(error)) }
+        }
+    }
+}
+
+glib_wrapper! {
+    pub struct FileEnumerator(Object<gio_ffi::GFileEnumerator>);
+
+    match fn {
+        get_type => || gio_ffi::g_file_enumerator_get_type(),
+    }
+}
+
+impl FileEnumerator {
+    /// Returns the next child, or `None` once all have been enumerated.
+    pub fn next_file(&self, cancellable: Option<&Cancellable>) -> Result<Option<FileInfo>, Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = gio_ffi::g_file_enumerator_next_file(self.to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(ret)) } else { Err(from_glib_full(error)) }
+        }
+    }
+}
+
+impl Iterator for FileEnumerator {
+    type Item = Result<FileInfo, Error>;
+
+    fn next(&mut self) -> Option<Result<FileInfo, Error>> {
+        match self.next_file(None) {
+            Ok(Some(info)) => Some(Ok(info)),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+glib_wrapper! {
+    pub struct FileInfo(Object<gio_ffi::GFileInfo>);
+
+    match fn {
+        get_type => || gio_ffi::g_file_info_get_type(),
+    }
+}
+
+impl FileInfo {
+    pub fn get_attribute_uint32(&self, attribute: &str) -> u32 {
+        unsafe {
+            gio_ffi::g_file_info_get_attribute_uint32(self.to_glib_none().0, attribute.to_glib_none().0)
+        }
+    }
+
+    pub fn get_file_type(&self) -> FileType {
+        unsafe {
+            from_glib(gio_ffi::g_file_info_get_file_type(self.to_glib_none().0))
+        }
+    }
+
+    pub fn get_name(&self) -> Option<PathBuf> {
+        unsafe {
+            from_glib_none(gio_ffi::g_file_info_get_name(self.to_glib_none().0))
+        }
+    }
+
+    pub fn get_size(&self) -> i64 {
+        unsafe {
+            gio_ffi::g_file_info_get_size(self.to_glib_none().0)
+        }
+    }
+
+    pub fn get_symlink_target(&self) -> Option<String> {
+        unsafe {
+            from_glib_none(gio_ffi::g_file_info_get_symlink_target(self.to_glib_none().0))
+        }
+    }
+}
+
+glib_wrapper! {
+    pub struct InputStream(Object<gio_ffi::GInputStream>);
+
+    match fn {
+        get_type => || gio_ffi::g_input_stream_get_type(),
+    }
+}
+
+impl InputStream {
+    /// Creates an in-memory stream over a copy of `data`.
+    pub fn from_data(data: &[u8]) -> InputStream {
+        unsafe {
+            // g_memdup() takes a 32-bit length
+            let copy = glib_ffi::g_malloc(data.len()) as *mut u8;
+            if !data.is_empty() {
+                ptr::copy_nonoverlapping(data.as_ptr(), copy, data.len());
+            }
+            from_glib_full(gio_ffi::g_memory_input_stream_new_from_data(copy, data.len() as isize, Some(glib_ffi::g_free)))
+        }
+    }
+
+    /// Creates a stream reading from `reader` on demand. The stream owns
+    /// the reader until it is closed or finalized.
+    pub fn from_read<R: Read + Send + 'static>(reader: R) -> InputStream {
+        read_input_stream::new(reader)
+    }
+}
+
+impl Read for InputStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let ret = gio_ffi::g_input_stream_read(self.to_glib_none().0, buf.as_mut_ptr(), buf.len(),
+                                                   ptr::null_mut(), &mut error);
+            if error.is_null() {
+                Ok(ret as usize)
+            } else {
+                Err(to_io_error(&from_glib_full(error)))
+            }
+        }
+    }
+}
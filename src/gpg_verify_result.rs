@@ -0,0 +1,83 @@
+use ffi;
+
+use GpgVerifyResult;
+use variant_utils::*;
+
+/// The details of a single signature in a `GpgVerifyResult`.
+///
+/// Built from the attributes listed in `OstreeGpgSignatureAttr`. If the
+/// signing key is not available, `user_name` and `user_email` hold OSTree's
+/// `[unknown name]` and `[unknown email]` placeholders.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Signature {
+    pub valid: bool,
+    pub sig_expired: bool,
+    pub key_expired: bool,
+    pub key_revoked: bool,
+    pub key_missing: bool,
+    pub fingerprint: String,
+    /// Creation time of the signature, in seconds since the epoch.
+    pub timestamp: i64,
+    /// Expiry time of the signature, or 0 if it does not expire.
+    pub exp_timestamp: i64,
+    pub pubkey_algo_name: String,
+    pub hash_algo_name: String,
+    pub user_name: String,
+    pub user_email: String,
+}
+
+impl GpgVerifyResult {
+    /// Returns the details of the signature at `signature_index`.
+    pub fn signature(&self, signature_index: u32) -> Option<Signature> {
+        if signature_index >= self.count_all() {
+            return None;
+        }
+        self.get_all(signature_index).map(|all| Signature {
+            valid: child_bool(&all, ffi::OSTREE_GPG_SIGNATURE_ATTR_VALID as usize),
+            sig_expired: child_bool(&all, ffi::OSTREE_GPG_SIGNATURE_ATTR_SIG_EXPIRED as usize),
+            key_expired: child_bool(&all, ffi::OSTREE_GPG_SIGNATURE_ATTR_KEY_EXPIRED as usize),
+            key_revoked: child_bool(&all, ffi::OSTREE_GPG_SIGNATURE_ATTR_KEY_REVOKED as usize),
+            key_missing: child_bool(&all, ffi::OSTREE_GPG_SIGNATURE_ATTR_KEY_MISSING as usize),
+            fingerprint: child_str(&all, ffi::OSTREE_GPG_SIGNATURE_ATTR_FINGERPRINT as usize),
+            timestamp: child_i64(&all, ffi::OSTREE_GPG_SIGNATURE_ATTR_TIMESTAMP as usize),
+            exp_timestamp: child_i64(&all, ffi::OSTREE_GPG_SIGNATURE_ATTR_EXP_TIMESTAMP as usize),
+            pubkey_algo_name: child_str(&all, ffi::OSTREE_GPG_SIGNATURE_ATTR_PUBKEY_ALGO_NAME as usize),
+            hash_algo_name: child_str(&all, ffi::OSTREE_GPG_SIGNATURE_ATTR_HASH_ALGO_NAME as usize),
+            user_name: child_str(&all, ffi::OSTREE_GPG_SIGNATURE_ATTR_USER_NAME as usize),
+            user_email: child_str(&all, ffi::OSTREE_GPG_SIGNATURE_ATTR_USER_EMAIL as usize),
+        })
+    }
+
+    /// Returns an iterator over the details of all signatures.
+    pub fn signatures<'a>(&'a self) -> Signatures<'a> {
+        Signatures {
+            result: self,
+            index: 0,
+            count: self.count_all(),
+        }
+    }
+}
+
+/// Iterator over the signatures of a `GpgVerifyResult`.
+pub struct Signatures<'a> {
+    result: &'a GpgVerifyResult,
+    index: u32,
+    count: u32,
+}
+
+impl<'a> Iterator for Signatures<'a> {
+    type Item = Signature;
+
+    fn next(&mut self) -> Option<Signature> {
+        if self.index >= self.count {
+            return None;
+        }
+        self.index += 1;
+        self.result.signature(self.index - 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.count - self.index) as usize;
+        (len, Some(len))
+    }
+}
@@ -0,0 +1,24 @@
+//! Conversions between `std::io` and GLib errors.
+
+use std::io;
+
+use glib::{Error, FileError};
+use glib::error::ErrorDomain;
+use glib_ffi;
+
+/// Converts an I/O error into a `G_FILE_ERROR`, keeping the errno if there
+/// is one.
+pub fn to_glib_error(err: &io::Error) -> Error {
+    let kind = match err.raw_os_error() {
+        Some(errno) => unsafe { <FileError as ErrorDomain>::from(glib_ffi::g_file_error_from_errno(errno) as i32) },
+        None => None,
+    };
+    Error::new(kind.unwrap_or(FileError::Failed), &err.to_string())
+}
+
+/// Converts a GLib error into an I/O error carrying its message.
+// io::Error::other() needs Rust 1.74.
+#[allow(unknown_lints, clippy::io_other_error)]
+pub fn to_io_error(err: &Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, err.to_string())
+}
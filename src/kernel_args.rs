@@ -0,0 +1,209 @@
+use std::fmt;
+use std::mem;
+use std::slice;
+
+/// A kernel command line, like the `options` line of a boot entry.
+///
+/// Arguments keep their order and may repeat, e.g. several `console=`. Each
+/// one is kept verbatim, quotes included, along with the whitespace before
+/// it and at the end of the line, so formatting a parsed line gives it back
+/// exactly. Added arguments are separated by a single space; removing one
+/// drops the whitespace before it, except at the start of the line.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KernelArgs {
+    args: Vec<String>,
+    // The whitespace before each argument.
+    separators: Vec<String>,
+    trailing: String,
+}
+
+impl KernelArgs {
+    pub fn new() -> KernelArgs {
+        KernelArgs::default()
+    }
+
+    /// Splits `options` on runs of whitespace outside of double quotes.
+    pub fn parse(options: &str) -> KernelArgs {
+        let mut args = KernelArgs::new();
+        let mut current = String::new();
+        let mut separator = String::new();
+        let mut quoted = false;
+        for c in options.chars() {
+            if c == '"' {
+                quoted = !quoted;
+            } else if c.is_whitespace() && !quoted {
+                if !current.is_empty() {
+                    args.args.push(mem::take(&mut current));
+                    args.separators.push(mem::take(&mut separator));
+                }
+                separator.push(c);
+                continue;
+            }
+            current.push(c);
+        }
+        if current.is_empty() {
+            args.trailing = separator;
+        } else {
+            args.args.push(current);
+            args.separators.push(separator);
+        }
+        args
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    /// The arguments as `(key, value)`, where `value` is `None` for
+    /// arguments without `=`.
+    pub fn iter<'a>(&'a self) -> Iter<'a> {
+        Iter(self.args.iter())
+    }
+
+    /// The values of all arguments named `key`, in order.
+    pub fn values<'a>(&'a self, key: &'a str) -> impl Iterator<Item = Option<&'a str>> + 'a {
+        self.iter().filter(move |&(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Whether an argument matches `arg`: `key` matches any argument with
+    /// that key, `key=value` only that exact value.
+    pub fn contains(&self, arg: &str) -> bool {
+        self.args.iter().any(|a| matches(a, arg))
+    }
+
+    /// Adds `arg` at the end, even if its key is already present.
+    pub fn append(&mut self, arg: &str) {
+        let separator = if self.args.is_empty() { "" } else { " " };
+        self.args.push(arg.to_owned());
+        self.separators.push(separator.to_owned());
+    }
+
+    /// Replaces all arguments with the key of `arg` by `arg`, at the position
+    /// of the first one, or appends it if there is none.
+    pub fn replace(&mut self, arg: &str) {
+        let key = split(arg).0;
+        match self.args.iter().position(|a| split(a).0 == key) {
+            Some(pos) => {
+                let mut first = true;
+                self.retain(|a| {
+                    if split(a).0 != key {
+                        return true;
+                    }
+                    let keep = first;
+                    first = false;
+                    keep
+                });
+                self.args[pos] = arg.to_owned();
+            }
+            None => self.append(arg),
+        }
+    }
+
+    /// Removes all arguments matching `arg` as in `contains()`, returning
+    /// whether there were any.
+    pub fn delete(&mut self, arg: &str) -> bool {
+        let len = self.args.len();
+        self.retain(|a| !matches(a, arg));
+        self.args.len() != len
+    }
+
+    pub fn as_slice(&self) -> &[String] {
+        &self.args
+    }
+
+    pub fn into_vec(self) -> Vec<String> {
+        self.args
+    }
+
+    /// Keeps the arguments for which `keep` returns true, with the
+    /// whitespace before them. The whitespace at the start of the line moves
+    /// to the first argument left.
+    fn retain<F: FnMut(&str) -> bool>(&mut self, mut keep: F) {
+        let args = mem::take(&mut self.args);
+        let separators = mem::take(&mut self.separators);
+        let mut leading = None;
+        for (arg, separator) in args.into_iter().zip(separators) {
+            if keep(&arg) {
+                let separator = leading.take().unwrap_or(separator);
+                self.args.push(arg);
+                self.separators.push(separator);
+            } else if self.args.is_empty() && leading.is_none() {
+                leading = Some(separator);
+            }
+        }
+        if let Some(leading) = leading {
+            self.trailing.insert_str(0, &leading);
+        }
+    }
+}
+
+/// Formats the arguments with the whitespace they were parsed with.
+impl fmt::Display for KernelArgs {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (separator, arg) in self.separators.iter().zip(&self.args) {
+            f.write_str(separator)?;
+            f.write_str(arg)?;
+        }
+        f.write_str(&self.trailing)
+    }
+}
+
+impl<'a> From<&'a str> for KernelArgs {
+    fn from(options: &'a str) -> KernelArgs {
+        KernelArgs::parse(options)
+    }
+}
+
+/// Separates the arguments with single spaces.
+impl From<Vec<String>> for KernelArgs {
+    fn from(args: Vec<String>) -> KernelArgs {
+        let mut kernel_args = KernelArgs::new();
+        for arg in args {
+            kernel_args.append(&arg);
+        }
+        kernel_args
+    }
+}
+
+impl From<KernelArgs> for Vec<String> {
+    fn from(args: KernelArgs) -> Vec<String> {
+        args.args
+    }
+}
+
+pub struct Iter<'a>(slice::Iter<'a, String>);
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (&'a str, Option<&'a str>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|arg| split(arg))
+    }
+}
+
+impl<'a> IntoIterator for &'a KernelArgs {
+    type Item = (&'a str, Option<&'a str>);
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+fn split(arg: &str) -> (&str, Option<&str>) {
+    match arg.find('=') {
+        Some(pos) => (&arg[..pos], Some(&arg[pos + 1..])),
+        None => (arg, None),
+    }
+}
+
+fn matches(arg: &str, pattern: &str) -> bool {
+    match split(pattern) {
+        (key, None) => split(arg).0 == key,
+        (_, Some(_)) => arg == pattern,
+    }
+}
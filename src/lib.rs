@@ -1,6 +1,8 @@
 
+#[macro_use]
+extern crate bitflags;
 extern crate libc;
-extern crate gio;
+extern crate gio as gio_crate;
 extern crate gio_sys as gio_ffi;
 #[macro_use]
 extern crate glib;
@@ -15,5 +17,116 @@ macro_rules! callback_guard {
 
 //pub use ffi::GUdevDeviceNumber as DeviceNumber;
 
+pub use glib::Error;
+
 pub use auto::*;
 mod auto;
+
+pub mod gio;
+
+#[cfg(feature = "libarchive")]
+pub use archive::{
+    ExportArchiveOptions,
+    ImportArchiveOptions,
+};
+#[cfg(feature = "libarchive")]
+mod archive;
+
+pub use checksum::Checksum;
+mod checksum;
+
+pub use checksum_file::{
+    checksum_file,
+    checksum_file_async,
+    checksum_file_from_input,
+    checksum_file_from_read,
+    checksum_file_future,
+    ChecksumFileFuture,
+};
+mod checksum_file;
+
+pub use checksum_input_stream::ChecksumReader;
+mod checksum_input_stream;
+
+pub use commit_diff::{
+    CommitDiff,
+    DiffSummary,
+    PathChange,
+    PathChangeKind,
+};
+mod commit_diff;
+
+pub use commit_metadata::{
+    CommitMetadata,
+    COMMIT_META_KEY_ENDOFLIFE,
+    COMMIT_META_KEY_ENDOFLIFE_REBASE,
+    COMMIT_META_KEY_REF_BINDING,
+    COMMIT_META_KEY_VERSION,
+};
+mod commit_metadata;
+
+pub use static_delta::{
+    StaticDeltaFallback,
+    StaticDeltaPart,
+    StaticDeltaSuperblock,
+};
+mod static_delta;
+
+pub use gpg_verify_result::{
+    Signature,
+    Signatures,
+};
+mod gpg_verify_result;
+
+mod bootconfig_parser;
+
+pub use deployment::Deployment;
+mod deployment;
+
+pub use diff::{
+    diff_dirs,
+    Diff,
+};
+mod diff;
+
+mod future_utils;
+
+mod io_utils;
+
+pub use kernel_args::KernelArgs;
+mod kernel_args;
+
+#[cfg(feature = "libarchive")]
+mod libarchive;
+
+pub use object_name::ObjectName;
+mod object_name;
+
+pub use origin::Origin;
+mod origin;
+
+mod ptr_array;
+
+mod read_input_stream;
+
+pub use repo::{
+    ContentObject,
+    WriteContentFuture,
+    WriteMetadataFuture,
+    Xattrs,
+};
+mod repo;
+mod repo_commit_modifier;
+
+pub use repo_file::TreeChild;
+mod repo_file;
+mod se_policy;
+mod sysroot;
+
+pub use sysroot_lock::{
+    LockFuture,
+    SysrootLock,
+};
+mod sysroot_lock;
+
+mod variant_utils;
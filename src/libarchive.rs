@@ -0,0 +1,36 @@
+//! The small part of libarchive needed to hand archives to OSTree.
+
+#![allow(non_camel_case_types)]
+
+use libc::{c_char, c_int, c_void, size_t, ssize_t};
+
+pub enum archive {}
+
+pub const ARCHIVE_OK: c_int = 0;
+pub const ARCHIVE_FATAL: c_int = -30;
+
+pub type archive_open_callback = Option<unsafe extern "C" fn(*mut archive, *mut c_void) -> c_int>;
+pub type archive_read_callback = Option<unsafe extern "C" fn(*mut archive, *mut c_void, *mut *const c_void) -> ssize_t>;
+pub type archive_write_callback = Option<unsafe extern "C" fn(*mut archive, *mut c_void, *const c_void, size_t) -> ssize_t>;
+pub type archive_close_callback = Option<unsafe extern "C" fn(*mut archive, *mut c_void) -> c_int>;
+
+#[link(name = "archive")]
+extern "C" {
+    pub fn archive_error_string(a: *mut archive) -> *const c_char;
+
+    pub fn archive_read_new() -> *mut archive;
+    pub fn archive_read_support_filter_all(a: *mut archive) -> c_int;
+    pub fn archive_read_support_format_all(a: *mut archive) -> c_int;
+    pub fn archive_read_open_filename(a: *mut archive, filename: *const c_char, block_size: size_t) -> c_int;
+    pub fn archive_read_open(a: *mut archive, client_data: *mut c_void, open: archive_open_callback,
+                             read: archive_read_callback, close: archive_close_callback) -> c_int;
+    pub fn archive_read_free(a: *mut archive) -> c_int;
+
+    pub fn archive_write_new() -> *mut archive;
+    pub fn archive_write_set_format_pax_restricted(a: *mut archive) -> c_int;
+    pub fn archive_write_open_filename(a: *mut archive, filename: *const c_char) -> c_int;
+    pub fn archive_write_open(a: *mut archive, client_data: *mut c_void, open: archive_open_callback,
+                              write: archive_write_callback, close: archive_close_callback) -> c_int;
+    pub fn archive_write_close(a: *mut archive) -> c_int;
+    pub fn archive_write_free(a: *mut archive) -> c_int;
+}
@@ -0,0 +1,78 @@
+use std::fmt;
+use std::ptr;
+use std::str::FromStr;
+
+use ffi;
+use glib::{Error, FileError};
+use glib::translate::*;
+
+use ObjectType;
+
+/// An object in a repository, identified by its checksum and type.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ObjectName {
+    pub checksum: String,
+    pub object_type: ObjectType,
+}
+
+impl ObjectName {
+    pub fn new<S: Into<String>>(checksum: S, object_type: ObjectType) -> ObjectName {
+        ObjectName {
+            checksum: checksum.into(),
+            object_type,
+        }
+    }
+}
+
+/// Formats the name as `CHECKSUM.TYPE`, e.g. `3a01….commit`.
+impl fmt::Display for ObjectName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}", self.checksum, self.object_type)
+    }
+}
+
+/// Parses the `CHECKSUM.TYPE` form.
+impl FromStr for ObjectName {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<ObjectName, Error> {
+        let (checksum, object_type) = match s.rfind('.') {
+            Some(pos) => (&s[..pos], &s[pos + 1..]),
+            None => return Err(Error::new(FileError::Inval, &format!("Invalid object name '{}'", s))),
+        };
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_validate_checksum_string(checksum.to_glib_none().0, &mut error);
+            if !error.is_null() {
+                return Err(from_glib_full(error));
+            }
+        }
+        Ok(ObjectName::new(checksum, object_type.parse()?))
+    }
+}
+
+impl fmt::Display for ObjectType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name: String = unsafe {
+            from_glib_none(ffi::ostree_object_type_to_string(self.to_glib()))
+        };
+        f.write_str(&name)
+    }
+}
+
+impl FromStr for ObjectType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<ObjectType, Error> {
+        // ostree_object_type_from_string() aborts on unknown names
+        match s {
+            "file" => Ok(ObjectType::File),
+            "dirtree" => Ok(ObjectType::DirTree),
+            "dirmeta" => Ok(ObjectType::DirMeta),
+            "commit" => Ok(ObjectType::Commit),
+            "tombstone-commit" => Ok(ObjectType::TombstoneCommit),
+            "commitmeta" => Ok(ObjectType::CommitMeta),
+            _ => Err(Error::new(FileError::Inval, &format!("Invalid object type '{}'", s))),
+        }
+    }
+}
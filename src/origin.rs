@@ -0,0 +1,325 @@
+use std::path::{Path, PathBuf};
+use std::ptr;
+
+use gio::FileExt;
+use glib::{Error, KeyFile, KEY_FILE_NONE};
+use glib::translate::*;
+use glib_ffi;
+
+use Deployment;
+use Sysroot;
+
+const ORIGIN_GROUP: &str = "origin";
+const KEY_REFSPEC: &str = "refspec";
+const KEY_OVERRIDE_COMMIT: &str = "override-commit";
+const KEY_UNCONFIGURED_STATE: &str = "unconfigured-state";
+
+/// The origin of a deployment: where it came from and where upgrades are
+/// pulled from.
+///
+/// All groups and keys are kept in file order with their raw values,
+/// including the ones this type has no accessor for, so `to_data()` gives
+/// back what was parsed except for what `GKeyFile` itself drops when
+/// libostree loads an origin file: comments, blank lines and whitespace
+/// around the `=`. Setting a key that exists changes it in place; a new key
+/// goes at the end of its group, and a new group at the end of the file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Origin {
+    groups: Vec<Group>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Group {
+    name: String,
+    // Raw, still escaped values.
+    entries: Vec<(String, String)>,
+}
+
+impl Origin {
+    /// An origin following `refspec`.
+    pub fn new(refspec: &str) -> Origin {
+        let mut origin = Origin::default();
+        origin.set_refspec(refspec);
+        origin
+    }
+
+    /// Parses the contents of an origin file.
+    pub fn parse(data: &str) -> Result<Origin, Error> {
+        let key_file = KeyFile::new();
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = glib_ffi::g_key_file_load_from_data(key_file.to_glib_none().0, data.as_ptr() as *const _, data.len(), KEY_FILE_NONE.to_glib(), &mut error);
+            if !error.is_null() {
+                return Err(from_glib_full(error));
+            }
+        }
+        Origin::from_key_file(&key_file)
+    }
+
+    /// Reads the origin file at `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Origin, Error> {
+        let key_file = KeyFile::new();
+        key_file.load_from_file(path, KEY_FILE_NONE)?;
+        Origin::from_key_file(&key_file)
+    }
+
+    /// Atomically replaces the file at `path` with `to_data()`.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let mut data = self.to_data()?.into_bytes();
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = glib_ffi::g_file_set_contents(path.as_ref().to_glib_none().0, data.as_mut_ptr(), data.len() as isize, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    pub fn from_key_file(key_file: &KeyFile) -> Result<Origin, Error> {
+        let mut origin = Origin::default();
+        for name in groups(key_file) {
+            let mut entries = Vec::new();
+            for key in keys(key_file, &name)? {
+                let value = key_file.get_value(&name, &key)?;
+                entries.push((key, value));
+            }
+            origin.groups.push(Group { name, entries });
+        }
+        Ok(origin)
+    }
+
+    pub fn to_key_file(&self) -> KeyFile {
+        let key_file = KeyFile::new();
+        for group in &self.groups {
+            if group.entries.is_empty() {
+                // g_key_file_set_value() is the only way to create a group
+                key_file.set_value(&group.name, "_", "");
+                let _ = key_file.remove_key(&group.name, "_");
+            }
+            for (key, value) in &group.entries {
+                key_file.set_value(&group.name, key, value);
+            }
+        }
+        key_file
+    }
+
+    /// Formats the origin as an origin file.
+    pub fn to_data(&self) -> Result<String, Error> {
+        let key_file = self.to_key_file();
+        unsafe {
+            let mut error = ptr::null_mut();
+            let data = glib_ffi::g_key_file_to_data(key_file.to_glib_none().0, ptr::null_mut(), &mut error);
+            if error.is_null() { Ok(from_glib_full(data)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    /// `origin/refspec`, e.g. `remote:fedora/x86_64/atomic-host`.
+    pub fn refspec(&self) -> Option<String> {
+        self.get(ORIGIN_GROUP, KEY_REFSPEC)
+    }
+
+    pub fn set_refspec(&mut self, refspec: &str) {
+        self.set(ORIGIN_GROUP, KEY_REFSPEC, refspec);
+    }
+
+    /// `origin/override-commit`, the commit to stay on instead of following
+    /// the ref.
+    pub fn override_commit(&self) -> Option<String> {
+        self.get(ORIGIN_GROUP, KEY_OVERRIDE_COMMIT)
+    }
+
+    pub fn set_override_commit(&mut self, checksum: Option<&str>) {
+        self.set_or_remove(ORIGIN_GROUP, KEY_OVERRIDE_COMMIT, checksum);
+    }
+
+    /// `origin/unconfigured-state`, a message explaining why upgrades are
+    /// disabled.
+    pub fn unconfigured_state(&self) -> Option<String> {
+        self.get(ORIGIN_GROUP, KEY_UNCONFIGURED_STATE)
+    }
+
+    pub fn set_unconfigured_state(&mut self, message: Option<&str>) {
+        self.set_or_remove(ORIGIN_GROUP, KEY_UNCONFIGURED_STATE, message);
+    }
+
+    /// The group names, in file order.
+    pub fn groups(&self) -> Vec<&str> {
+        self.groups.iter().map(|group| &group.name[..]).collect()
+    }
+
+    /// The keys of `group`, in file order.
+    pub fn keys(&self, group: &str) -> Vec<&str> {
+        self.group(group).map_or_else(Vec::new, |group| group.entries.iter().map(|(key, _)| &key[..]).collect())
+    }
+
+    /// The value of `group/key`, unescaped like `g_key_file_get_string()`
+    /// does. Invalid escape sequences are kept as they are.
+    pub fn get(&self, group: &str, key: &str) -> Option<String> {
+        self.group(group)
+            .and_then(|group| group.entries.iter().find(|(k, _)| k == key))
+            .map(|(_, value)| unescape(value))
+    }
+
+    /// Sets `group/key` to `value`, escaped like `g_key_file_set_string()`
+    /// does.
+    pub fn set(&mut self, group: &str, key: &str, value: &str) {
+        let value = escape(value);
+        let index = match self.groups.iter().position(|g| g.name == group) {
+            Some(index) => index,
+            None => {
+                self.groups.push(Group { name: group.to_owned(), entries: Vec::new() });
+                self.groups.len() - 1
+            }
+        };
+        let entries = &mut self.groups[index].entries;
+        match entries.iter_mut().find(|(k, _)| k == key) {
+            Some(entry) => entry.1 = value,
+            None => entries.push((key.to_owned(), value)),
+        }
+    }
+
+    /// Removes `group/key`, returning whether it was there. The group stays,
+    /// even if it becomes empty.
+    pub fn remove(&mut self, group: &str, key: &str) -> bool {
+        match self.groups.iter_mut().find(|g| g.name == group) {
+            Some(group) => {
+                let len = group.entries.len();
+                group.entries.retain(|(k, _)| k != key);
+                group.entries.len() != len
+            }
+            None => false,
+        }
+    }
+
+    fn group(&self, name: &str) -> Option<&Group> {
+        self.groups.iter().find(|group| group.name == name)
+    }
+
+    fn set_or_remove(&mut self, group: &str, key: &str, value: Option<&str>) {
+        match value {
+            Some(value) => self.set(group, key, value),
+            None => {
+                self.remove(group, key);
+            }
+        }
+    }
+}
+
+impl<'a> From<&'a Origin> for KeyFile {
+    fn from(origin: &'a Origin) -> KeyFile {
+        origin.to_key_file()
+    }
+}
+
+impl Sysroot {
+    /// Like `origin_new_from_refspec()`, returning a typed `Origin`.
+    pub fn new_origin(&self, refspec: &str) -> Result<Origin, Error> {
+        match self.origin_new_from_refspec(refspec) {
+            Some(key_file) => Origin::from_key_file(&key_file),
+            None => Ok(Origin::new(refspec)),
+        }
+    }
+
+    /// The path of the origin file of `deployment`, as given by
+    /// `get_deployment_origin_path()`.
+    pub fn deployment_origin_path(&self, deployment: &Deployment) -> Option<PathBuf> {
+        self.get_deployment_directory(deployment)
+            .and_then(|dir| Sysroot::get_deployment_origin_path(&dir))
+            .and_then(|file| file.get_path())
+    }
+
+    /// Reads the origin file of `deployment`, if it has one.
+    pub fn load_deployment_origin(&self, deployment: &Deployment) -> Result<Option<Origin>, Error> {
+        match self.deployment_origin_path(deployment) {
+            Some(ref path) if path.exists() => Origin::load(path).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Replaces the origin file of `deployment` with `origin`, and the origin
+    /// `deployment` holds in memory.
+    pub fn save_deployment_origin(&self, deployment: &Deployment, origin: &Origin) -> Result<(), Error> {
+        if let Some(path) = self.deployment_origin_path(deployment) {
+            origin.save(path)?;
+        }
+        deployment.set_origin_from(origin);
+        Ok(())
+    }
+}
+
+impl Deployment {
+    /// The parsed origin of the deployment, if it has one.
+    pub fn origin(&self) -> Result<Option<Origin>, Error> {
+        match self.get_origin() {
+            Some(key_file) => Origin::from_key_file(&key_file).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub fn set_origin_from(&self, origin: &Origin) {
+        self.set_origin(&origin.to_key_file());
+    }
+}
+
+/// Escapes `value` like `g_key_file_set_string()`.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    let mut leading_space = true;
+    for c in value.chars() {
+        match c {
+            ' ' if leading_space => escaped.push_str("\\s"),
+            '\t' if leading_space => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\\' => {
+                escaped.push_str("\\\\");
+                leading_space = false;
+            }
+            c => {
+                escaped.push(c);
+                leading_space = false;
+            }
+        }
+    }
+    escaped
+}
+
+/// Unescapes `value` like `g_key_file_get_string()`.
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => unescaped.push(' '),
+            Some('n') => unescaped.push('\n'),
+            Some('t') => unescaped.push('\t'),
+            Some('r') => unescaped.push('\r'),
+            Some('\\') => unescaped.push('\\'),
+            Some(c) => {
+                unescaped.push('\\');
+                unescaped.push(c);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+fn groups(key_file: &KeyFile) -> Vec<String> {
+    unsafe {
+        let mut len = 0;
+        let groups = glib_ffi::g_key_file_get_groups(key_file.to_glib_none().0, &mut len);
+        FromGlibPtrContainer::from_glib_full_num(groups, len)
+    }
+}
+
+fn keys(key_file: &KeyFile, group: &str) -> Result<Vec<String>, Error> {
+    unsafe {
+        let mut len = 0;
+        let mut error = ptr::null_mut();
+        let keys = glib_ffi::g_key_file_get_keys(key_file.to_glib_none().0, group.to_glib_none().0, &mut len, &mut error);
+        if error.is_null() { Ok(FromGlibPtrContainer::from_glib_full_num(keys, len)) } else { Err(from_glib_full(error)) }
+    }
+}
@@ -0,0 +1,30 @@
+//! Conversions between `GPtrArray`s and vectors.
+
+use std::slice;
+
+use glib::translate::*;
+use glib_ffi;
+
+/// Converts the elements of `arr` with `f` and unrefs it.
+pub unsafe fn take<T, F: FnMut(glib_ffi::gpointer) -> T>(arr: *mut glib_ffi::GPtrArray, mut f: F) -> Vec<T> {
+    // pdata is NULL for an empty array
+    let ret = if (*arr).len == 0 {
+        Vec::new()
+    } else {
+        slice::from_raw_parts((*arr).pdata, (*arr).len as usize).iter()
+            .map(|&ptr| f(ptr))
+            .collect()
+    };
+    glib_ffi::g_ptr_array_unref(arr);
+    ret
+}
+
+/// Returns a new array borrowing the pointers of `items`, which the caller
+/// must unref before `items` goes away.
+pub unsafe fn from_slice<'a, T: 'a + ToGlibPtr<'a, *mut U>, U>(items: &'a [T]) -> *mut glib_ffi::GPtrArray {
+    let arr = glib_ffi::g_ptr_array_sized_new(items.len() as u32);
+    for item in items {
+        glib_ffi::g_ptr_array_add(arr, item.to_glib_none().0 as glib_ffi::gpointer);
+    }
+    arr
+}
@@ -0,0 +1,181 @@
+//! A `GInputStream` subclass pulling its data from a Rust `Read`.
+//!
+//! GIO calls `read_fn` whenever the stream is read, so the reader is only
+//! ever asked for as much data as the consumer wants; nothing is buffered.
+
+use std::any::Any;
+use std::io::{self, Read};
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+use std::sync::Once;
+
+use gio;
+use gio_ffi;
+use glib::{Error, FileError};
+use glib::translate::*;
+use glib_ffi;
+use gobject_ffi;
+use libc::{c_void, size_t, ssize_t};
+
+use io_utils::to_glib_error;
+
+#[repr(C)]
+struct ReadInputStream {
+    parent: gio_ffi::GInputStream,
+    // Both zeroed by GType until set; owned by the instance.
+    reader: *mut Box<dyn Read>,
+    panic: *mut Box<dyn Any + Send>,
+}
+
+#[repr(C)]
+struct ReadInputStreamClass {
+    parent_class: gio_ffi::GInputStreamClass,
+}
+
+static mut PARENT_CLASS: *const gobject_ffi::GObjectClass = ptr::null();
+
+fn get_type() -> glib_ffi::GType {
+    static REGISTER: Once = Once::new();
+    static mut TYPE: glib_ffi::GType = 0;
+    unsafe {
+        REGISTER.call_once(|| {
+            TYPE = gobject_ffi::g_type_register_static_simple(
+                gio_ffi::g_input_stream_get_type(),
+                b"OstreeRsReadInputStream\0".as_ptr() as *const _,
+                mem::size_of::<ReadInputStreamClass>() as u32,
+                Some(class_init),
+                mem::size_of::<ReadInputStream>() as u32,
+                None,
+                gobject_ffi::GTypeFlags::empty());
+        });
+        TYPE
+    }
+}
+
+unsafe extern "C" fn class_init(klass: glib_ffi::gpointer, _class_data: glib_ffi::gpointer) {
+    PARENT_CLASS = gobject_ffi::g_type_class_peek_parent(klass) as *const _;
+    let object_class = klass as *mut gobject_ffi::GObjectClass;
+    (*object_class).finalize = Some(finalize);
+    let stream_class = klass as *mut gio_ffi::GInputStreamClass;
+    (*stream_class).read_fn = Some(read_fn);
+    (*stream_class).close_fn = Some(close_fn);
+}
+
+unsafe extern "C" fn read_fn(stream: *mut gio_ffi::GInputStream, buffer: *mut c_void, count: size_t, cancellable: *mut gio_ffi::GCancellable, error: *mut *mut glib_ffi::GError) -> ssize_t {
+    callback_guard!();
+    let this = stream as *mut ReadInputStream;
+    if gio_ffi::g_cancellable_set_error_if_cancelled(cancellable, error) != glib_ffi::GFALSE {
+        return -1;
+    }
+    if (*this).reader.is_null() {
+        set_error(error, Error::new(FileError::Failed, "The reader of the stream is gone"));
+        return -1;
+    }
+    let reader = &mut **(*this).reader;
+    let buf = slice::from_raw_parts_mut(buffer as *mut u8, count);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| loop {
+        match reader.read(buf) {
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            result => return result,
+        }
+    }));
+    match result {
+        Ok(Ok(len)) => len as ssize_t,
+        Ok(Err(err)) => {
+            set_error(error, to_glib_error(&err));
+            -1
+        }
+        Err(payload) => {
+            // A reader that panicked is in an unknown state: stop using it.
+            drop(Box::from_raw((*this).reader));
+            (*this).reader = ptr::null_mut();
+            if (*this).panic.is_null() {
+                (*this).panic = Box::into_raw(Box::new(payload));
+            }
+            set_error(error, Error::new(FileError::Failed, "The reader of the stream panicked"));
+            -1
+        }
+    }
+}
+
+unsafe extern "C" fn close_fn(stream: *mut gio_ffi::GInputStream, _cancellable: *mut gio_ffi::GCancellable, _error: *mut *mut glib_ffi::GError) -> glib_ffi::gboolean {
+    callback_guard!();
+    drop(take_reader(stream as *mut ReadInputStream));
+    glib_ffi::GTRUE
+}
+
+unsafe extern "C" fn finalize(object: *mut gobject_ffi::GObject) {
+    callback_guard!();
+    let this = object as *mut ReadInputStream;
+    drop(take_reader(this));
+    drop(take_panic(this));
+    if let Some(finalize) = (*PARENT_CLASS).finalize {
+        finalize(object);
+    }
+}
+
+fn instance(stream: &gio::InputStream) -> *mut ReadInputStream {
+    let ptr: *mut gio_ffi::GInputStream = stream.to_glib_none().0;
+    ptr as *mut _
+}
+
+unsafe fn take_reader(this: *mut ReadInputStream) -> Option<Box<dyn Read>> {
+    let reader = mem::replace(&mut (*this).reader, ptr::null_mut());
+    if reader.is_null() { None } else { Some(*Box::from_raw(reader)) }
+}
+
+unsafe fn take_panic(this: *mut ReadInputStream) -> Option<Box<dyn Any + Send>> {
+    let payload = mem::replace(&mut (*this).panic, ptr::null_mut());
+    if payload.is_null() { None } else { Some(*Box::from_raw(payload)) }
+}
+
+unsafe fn set_error(error: *mut *mut glib_ffi::GError, err: Error) {
+    if !error.is_null() {
+        *error = err.to_glib_full() as *mut _;
+    }
+}
+
+/// Creates a stream reading from `reader`. The stream owns the reader until
+/// it is closed or finalized.
+pub fn new<R: Read + Send + 'static>(reader: R) -> gio::InputStream {
+    unsafe { new_unchecked(Box::new(reader)) }
+}
+
+unsafe fn new_unchecked(reader: Box<dyn Read>) -> gio::InputStream {
+    let object = gobject_ffi::g_object_newv(get_type(), 0, ptr::null_mut());
+    (*(object as *mut ReadInputStream)).reader = Box::into_raw(Box::new(reader));
+    from_glib_full(object as *mut gio_ffi::GInputStream)
+}
+
+/// Calls `f` with a stream reading from `reader`, for synchronous calls that
+/// are done with the stream when they return. The reader is detached from
+/// the stream once `f` returns, so later reads fail instead of touching a
+/// dropped reader, and a panic of the reader is resumed at that point.
+pub fn with_stream<R: Read, T, F: FnOnce(&gio::InputStream) -> T>(mut reader: R, f: F) -> T {
+    struct Detach(gio::InputStream);
+
+    impl Drop for Detach {
+        fn drop(&mut self) {
+            unsafe {
+                drop(take_reader(instance(&self.0)));
+            }
+        }
+    }
+
+    let ret;
+    let payload;
+    {
+        let reader: Box<dyn Read + '_> = Box::new(&mut reader);
+        // Sound because `Detach` drops the reader before `reader` goes away,
+        // even when `f` panics.
+        let stream = Detach(unsafe { new_unchecked(mem::transmute::<Box<dyn Read + '_>, Box<dyn Read + 'static>>(reader)) });
+        ret = f(&stream.0);
+        payload = unsafe { take_panic(instance(&stream.0)) };
+    }
+    if let Some(payload) = payload {
+        panic::resume_unwind(payload);
+    }
+    ret
+}
@@ -0,0 +1,378 @@
+use std::future::Future;
+use std::io::Read;
+use std::path::Path;
+use std::pin::Pin;
+use std::ptr;
+use std::task::{Context, Poll};
+
+use ffi;
+use gio;
+use gio_ffi;
+use glib::{Error, Variant};
+use glib::object::IsA;
+use glib::translate::*;
+use glib_ffi;
+use gobject_ffi;
+
+use Checksum;
+use CommitMetadata;
+use ObjectName;
+use ObjectType;
+use Repo;
+use future_utils::{Abandoned, FutureState};
+use read_input_stream;
+use variant_utils;
+
+/// Extended attributes as `(name, value)` pairs, as stored by OSTree (names
+/// include their trailing NUL).
+pub type Xattrs = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// A content object loaded with `Repo::load_file()`.
+pub struct ContentObject {
+    /// The file contents; `None` for symbolic links.
+    pub input: Option<gio::InputStream>,
+    pub file_info: gio::FileInfo,
+    pub xattrs: Xattrs,
+}
+
+impl ContentObject {
+    pub fn mode(&self) -> u32 {
+        self.file_info.get_attribute_uint32("unix::mode")
+    }
+
+    pub fn uid(&self) -> u32 {
+        self.file_info.get_attribute_uint32("unix::uid")
+    }
+
+    pub fn gid(&self) -> u32 {
+        self.file_info.get_attribute_uint32("unix::gid")
+    }
+
+    pub fn size(&self) -> u64 {
+        self.file_info.get_size() as u64
+    }
+
+    pub fn symlink_target(&self) -> Option<String> {
+        self.file_info.get_symlink_target()
+    }
+}
+
+impl Repo {
+    /// Imports GPG keys from `source_stream` into the keyring of remote
+    /// `name`, returning the number of keys imported.
+    ///
+    /// Without a `source_stream` the keys are taken from the user's default
+    /// keyring. If `key_ids` is empty, all keys in the source are imported.
+    pub fn remote_gpg_import<T: IsA<gio::InputStream>>(&self, name: &str, source_stream: Option<&T>, key_ids: &[&str], cancellable: Option<&gio::Cancellable>) -> Result<u32, Error> {
+        let key_ids = if key_ids.is_empty() { None } else { Some(key_ids) };
+        unsafe {
+            let mut out_imported = 0;
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_remote_gpg_import(self.to_glib_none().0, name.to_glib_none().0, source_stream.to_glib_none().0, key_ids.to_glib_none().0, &mut out_imported, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(out_imported) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    /// Like `remote_gpg_import()`, reading the keys from `source`, e.g. an
+    /// opened keyring file or an embedded byte slice.
+    pub fn remote_gpg_import_from_read<R: Read>(&self, name: &str, source: R, key_ids: &[&str], cancellable: Option<&gio::Cancellable>) -> Result<u32, Error> {
+        read_input_stream::with_stream(source, |stream| self.remote_gpg_import(name, Some(stream), key_ids, cancellable))
+    }
+
+    /// Signs the summary file of the repository with the GnuPG keys
+    /// `key_id`. `homedir` overrides the GnuPG home directory.
+    pub fn add_gpg_signature_summary<'a, T: Into<Option<&'a Path>>>(&self, key_id: &[&str], homedir: T, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        let homedir = homedir.into();
+        let homedir = homedir.to_glib_none();
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_add_gpg_signature_summary(self.to_glib_none().0, key_id.to_glib_none().0, homedir.0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    /// Signs commit `commit_checksum` with the GnuPG key `key_id`. `homedir`
+    /// overrides the GnuPG home directory.
+    pub fn sign_commit<'a, T: Into<Option<&'a Path>>>(&self, commit_checksum: &str, key_id: &str, homedir: T, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        let homedir = homedir.into();
+        let homedir = homedir.to_glib_none();
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_sign_commit(self.to_glib_none().0, commit_checksum.to_glib_none().0, key_id.to_glib_none().0, homedir.0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    /// Signs the static delta from `from_commit` to `to_commit` with the GnuPG
+    /// key `key_id`. `homedir` overrides the GnuPG home directory.
+    pub fn sign_delta<'a, T: Into<Option<&'a Path>>>(&self, from_commit: &str, to_commit: &str, key_id: &str, homedir: T, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        let homedir = homedir.into();
+        let homedir = homedir.to_glib_none();
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_sign_delta(self.to_glib_none().0, from_commit.to_glib_none().0, to_commit.to_glib_none().0, key_id.to_glib_none().0, homedir.0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    /// Reads the detached metadata of commit `checksum`. A commit without
+    /// detached metadata yields an empty map.
+    pub fn commit_detached_metadata(&self, checksum: &str, cancellable: Option<&gio::Cancellable>) -> Result<CommitMetadata, Error> {
+        match self.read_commit_detached_metadata(checksum, cancellable)? {
+            Some(metadata) => CommitMetadata::from_variant(&metadata),
+            None => Ok(CommitMetadata::new()),
+        }
+    }
+
+    /// Replaces the detached metadata of commit `checksum`. An empty map
+    /// removes it.
+    pub fn set_commit_detached_metadata(&self, checksum: &str, metadata: &CommitMetadata, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        let metadata = if metadata.is_empty() { None } else { Some(metadata.to_variant()) };
+        self.write_commit_detached_metadata(checksum, metadata.as_ref(), cancellable)
+    }
+
+    /// Loads the content object `checksum`, with its contents as a stream.
+    pub fn load_file(&self, checksum: &str, cancellable: Option<&gio::Cancellable>) -> Result<ContentObject, Error> {
+        let (input, file_info, xattrs): (_, _, Option<Variant>) = unsafe {
+            let mut out_input = ptr::null_mut();
+            let mut out_file_info = ptr::null_mut();
+            let mut out_xattrs = ptr::null_mut();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_load_file(self.to_glib_none().0, checksum.to_glib_none().0, &mut out_input, &mut out_file_info, &mut out_xattrs, cancellable.to_glib_none().0, &mut error);
+            if !error.is_null() {
+                return Err(from_glib_full(error));
+            }
+            (from_glib_full(out_input), from_glib_full(out_file_info), from_glib_full(out_xattrs))
+        };
+
+        let xattrs = xattrs.map(|xattrs| variant_utils::xattrs(&xattrs)).unwrap_or_default();
+
+        Ok(ContentObject {
+            input,
+            file_info,
+            xattrs,
+        })
+    }
+
+    /// Stores the content object read from `object_input`, a stream of
+    /// `length` bytes in the OSTree content stream format, and returns its
+    /// checksum. If `expected_checksum` is given, it is verified.
+    pub fn write_content<'a, T: Into<Option<&'a str>>, U: IsA<gio::InputStream>>(&self, expected_checksum: T, object_input: &U, length: u64, cancellable: Option<&gio::Cancellable>) -> Result<Checksum, Error> {
+        let expected_checksum = expected_checksum.into();
+        let expected_checksum = expected_checksum.to_glib_none();
+        unsafe {
+            let mut out_csum = ptr::null_mut();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_write_content(self.to_glib_none().0, expected_checksum.0, object_input.to_glib_none().0, length, &mut out_csum, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(out_csum)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    /// Like `write_content()`, reading the first `length` bytes of `object`.
+    pub fn write_content_from_read<'a, T: Into<Option<&'a str>>, R: Read>(&self, expected_checksum: T, object: R, length: u64, cancellable: Option<&gio::Cancellable>) -> Result<Checksum, Error> {
+        read_input_stream::with_stream(object.take(length), |stream| self.write_content(expected_checksum, stream, length, cancellable))
+    }
+
+    /// Like `write_content()`, storing `object`.
+    pub fn write_content_from_data<'a, T: Into<Option<&'a str>>>(&self, expected_checksum: T, object: &[u8], cancellable: Option<&gio::Cancellable>) -> Result<Checksum, Error> {
+        let stream = gio::InputStream::from_data(object);
+        self.write_content(expected_checksum, &stream, object.len() as u64, cancellable)
+    }
+
+    /// Like `write_content_trusted()`, reading the first `length` bytes of
+    /// `object`.
+    pub fn write_content_trusted_from_read<R: Read>(&self, checksum: &str, object: R, length: u64, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        read_input_stream::with_stream(object.take(length), |stream| self.write_content_trusted(checksum, stream, length, cancellable))
+    }
+
+    /// Stores the metadata object `object` of type `objtype` and returns its
+    /// checksum. If `expected_checksum` is given, it is verified.
+    pub fn write_metadata<'a, T: Into<Option<&'a str>>>(&self, objtype: ObjectType, expected_checksum: T, object: &Variant, cancellable: Option<&gio::Cancellable>) -> Result<Checksum, Error> {
+        let expected_checksum = expected_checksum.into();
+        let expected_checksum = expected_checksum.to_glib_none();
+        unsafe {
+            let mut out_csum = ptr::null_mut();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_write_metadata(self.to_glib_none().0, objtype.to_glib(), expected_checksum.0, object.to_glib_none().0, &mut out_csum, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(from_glib_full(out_csum)) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    /// Like `write_metadata_stream_trusted()`, reading the first `length`
+    /// bytes of `object`.
+    pub fn write_metadata_stream_trusted_from_read<R: Read>(&self, objtype: ObjectType, checksum: &str, object: R, length: u64, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        read_input_stream::with_stream(object.take(length), |stream| self.write_metadata_stream_trusted(objtype, checksum, stream, length, cancellable))
+    }
+
+    /// Asynchronous version of `write_content()`. `callback` is invoked from
+    /// the thread-default main context once the object has been written.
+    pub fn write_content_async<'a, T: Into<Option<&'a str>>, U: IsA<gio::InputStream>, F: FnOnce(Result<Checksum, Error>) + 'static>(&self, expected_checksum: T, object: &U, length: u64, cancellable: Option<&gio::Cancellable>, callback: F) {
+        let expected_checksum = expected_checksum.into();
+        let expected_checksum = expected_checksum.to_glib_none();
+        let user_data: Box<Box<F>> = Box::new(Box::new(callback));
+        unsafe {
+            ffi::ostree_repo_write_content_async(self.to_glib_none().0, expected_checksum.0, object.to_glib_none().0, length, cancellable.to_glib_none().0, Some(write_content_async_trampoline::<F>), Box::into_raw(user_data) as glib_ffi::gpointer);
+        }
+    }
+
+    /// Asynchronous version of `write_metadata()`. `callback` is invoked from
+    /// the thread-default main context once the object has been written.
+    pub fn write_metadata_async<'a, T: Into<Option<&'a str>>, F: FnOnce(Result<Checksum, Error>) + 'static>(&self, objtype: ObjectType, expected_checksum: T, object: &Variant, cancellable: Option<&gio::Cancellable>, callback: F) {
+        let expected_checksum = expected_checksum.into();
+        let expected_checksum = expected_checksum.to_glib_none();
+        let user_data: Box<Box<F>> = Box::new(Box::new(callback));
+        unsafe {
+            ffi::ostree_repo_write_metadata_async(self.to_glib_none().0, objtype.to_glib(), expected_checksum.0, object.to_glib_none().0, cancellable.to_glib_none().0, Some(write_metadata_async_trampoline::<F>), Box::into_raw(user_data) as glib_ffi::gpointer);
+        }
+    }
+
+    /// Returns a future resolving to the result of `write_content_async()`,
+    /// for use with a GLib main context based executor. Several writes can be
+    /// in flight at once.
+    pub fn write_content_future<'a, T: Into<Option<&'a str>>, U: IsA<gio::InputStream>>(&self, expected_checksum: T, object: &U, length: u64) -> WriteContentFuture {
+        WriteContentFuture {
+            repo: self.clone(),
+            expected_checksum: expected_checksum.into().map(str::to_owned),
+            object: unsafe { from_glib_none(object.to_glib_none().0) },
+            length,
+            cancellable: gio::Cancellable::new(),
+            state: FutureState::new(),
+        }
+    }
+
+    /// Returns a future resolving to the result of `write_metadata_async()`,
+    /// for use with a GLib main context based executor.
+    pub fn write_metadata_future<'a, T: Into<Option<&'a str>>>(&self, objtype: ObjectType, expected_checksum: T, object: &Variant) -> WriteMetadataFuture {
+        WriteMetadataFuture {
+            repo: self.clone(),
+            objtype,
+            expected_checksum: expected_checksum.into().map(str::to_owned),
+            object: object.clone(),
+            cancellable: gio::Cancellable::new(),
+            state: FutureState::new(),
+        }
+    }
+
+    /// Completes the current transaction, making the objects written in it
+    /// visible.
+    pub fn commit_transaction(&self, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_commit_transaction(self.to_glib_none().0, ptr::null_mut(), cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    /// Copies `objects` from `source` within a single transaction.
+    ///
+    /// Objects that fail to import are skipped and returned along with their
+    /// error; the rest are committed. With `trusted`, checksums of the
+    /// imported objects are not verified.
+    pub fn import_objects_from<I: IntoIterator<Item = ObjectName>>(&self, source: &Repo, objects: I, trusted: bool, cancellable: Option<&gio::Cancellable>) -> Result<Vec<(ObjectName, Error)>, Error> {
+        self.prepare_transaction(cancellable)?;
+
+        let mut failures = Vec::new();
+        for object in objects {
+            if let Err(err) = self.import_object_from_with_trust(source, object.object_type, &object.checksum, trusted, cancellable) {
+                failures.push((object, err));
+            }
+        }
+
+        match self.commit_transaction(cancellable) {
+            Ok(()) => Ok(failures),
+            Err(err) => {
+                let _ = self.abort_transaction(cancellable);
+                Err(err)
+            }
+        }
+    }
+}
+
+/// Future returned by `Repo::write_content_future()`.
+///
+/// Like `ChecksumFileFuture`, it only makes progress while the thread-default
+/// main context is iterated. Dropping it before completion cancels the write.
+pub struct WriteContentFuture {
+    repo: Repo,
+    expected_checksum: Option<String>,
+    object: gio::InputStream,
+    length: u64,
+    cancellable: gio::Cancellable,
+    state: FutureState<Result<Checksum, Error>>,
+}
+
+impl Future for WriteContentFuture {
+    type Output = Result<Checksum, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Checksum, Error>> {
+        let this = self.get_mut();
+        this.state.poll(cx, || {
+            let state = this.state.clone();
+            this.repo.write_content_async(this.expected_checksum.as_ref().map(|s| &s[..]), &this.object, this.length, Some(&this.cancellable), move |result| {
+                let _ = state.complete(result);
+            });
+        })
+    }
+}
+
+impl Drop for WriteContentFuture {
+    fn drop(&mut self) {
+        if let Abandoned::Running = self.state.abandon() {
+            self.cancellable.cancel();
+        }
+    }
+}
+
+/// Future returned by `Repo::write_metadata_future()`.
+///
+/// Dropping it before completion cancels the write.
+pub struct WriteMetadataFuture {
+    repo: Repo,
+    objtype: ObjectType,
+    expected_checksum: Option<String>,
+    object: Variant,
+    cancellable: gio::Cancellable,
+    state: FutureState<Result<Checksum, Error>>,
+}
+
+impl Future for WriteMetadataFuture {
+    type Output = Result<Checksum, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Checksum, Error>> {
+        let this = self.get_mut();
+        this.state.poll(cx, || {
+            let state = this.state.clone();
+            this.repo.write_metadata_async(this.objtype, this.expected_checksum.as_ref().map(|s| &s[..]), &this.object, Some(&this.cancellable), move |result| {
+                let _ = state.complete(result);
+            });
+        })
+    }
+}
+
+impl Drop for WriteMetadataFuture {
+    fn drop(&mut self) {
+        if let Abandoned::Running = self.state.abandon() {
+            self.cancellable.cancel();
+        }
+    }
+}
+
+unsafe extern "C" fn write_content_async_trampoline<F: FnOnce(Result<Checksum, Error>) + 'static>(source_object: *mut gobject_ffi::GObject, res: *mut gio_ffi::GAsyncResult, user_data: glib_ffi::gpointer) {
+    callback_guard!();
+    let callback: Box<Box<F>> = Box::from_raw(user_data as *mut _);
+    let mut out_csum = ptr::null_mut();
+    let mut error = ptr::null_mut();
+    let _ = ffi::ostree_repo_write_content_finish(source_object as *mut _, res, &mut out_csum, &mut error);
+    let result = if error.is_null() { Ok(from_glib_full(out_csum)) } else { Err(from_glib_full(error)) };
+    callback(result);
+}
+
+unsafe extern "C" fn write_metadata_async_trampoline<F: FnOnce(Result<Checksum, Error>) + 'static>(source_object: *mut gobject_ffi::GObject, res: *mut gio_ffi::GAsyncResult, user_data: glib_ffi::gpointer) {
+    callback_guard!();
+    let callback: Box<Box<F>> = Box::from_raw(user_data as *mut _);
+    let mut out_csum = ptr::null_mut();
+    let mut error = ptr::null_mut();
+    let _ = ffi::ostree_repo_write_metadata_finish(source_object as *mut _, res, &mut out_csum, &mut error);
+    let result = if error.is_null() { Ok(from_glib_full(out_csum)) } else { Err(from_glib_full(error)) };
+    callback(result);
+}
@@ -0,0 +1,17 @@
+use std::ptr;
+
+use ffi;
+use glib::translate::*;
+
+use RepoCommitModifier;
+use RepoCommitModifierFlags;
+
+impl RepoCommitModifier {
+    /// Creates a modifier without a commit filter, so every file is
+    /// included.
+    pub fn new(flags: RepoCommitModifierFlags) -> RepoCommitModifier {
+        unsafe {
+            from_glib_full(ffi::ostree_repo_commit_modifier_new(flags.to_glib(), None, ptr::null_mut(), None))
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::ptr;
+
+use ffi;
+use gio;
+use glib::{Error, Variant};
+use glib::translate::*;
+
+use RepoFile;
+use Xattrs;
+use variant_utils;
+
+impl RepoFile {
+    /// Returns the extended attributes of the file.
+    pub fn get_xattrs(&self, cancellable: Option<&gio::Cancellable>) -> Result<Xattrs, Error> {
+        let xattrs: Option<Variant> = unsafe {
+            let mut out_xattrs = ptr::null_mut();
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_repo_file_get_xattrs(self.to_glib_none().0, &mut out_xattrs, cancellable.to_glib_none().0, &mut error);
+            if !error.is_null() {
+                return Err(from_glib_full(error));
+            }
+            from_glib_full(out_xattrs)
+        };
+        Ok(xattrs.map(|xattrs| variant_utils::xattrs(&xattrs)).unwrap_or_default())
+    }
+
+    /// Looks up the child `name` of this directory.
+    pub fn tree_find_child(&self, name: &str) -> Result<Option<TreeChild>, Error> {
+        self.ensure_resolved()?;
+        let (index, is_dir): (i32, bool) = unsafe {
+            let mut is_dir = 0;
+            let mut out_container = ptr::null_mut();
+            let index = ffi::ostree_repo_file_tree_find_child(self.to_glib_none().0, name.to_glib_none().0, &mut is_dir, &mut out_container);
+            let _: Option<Variant> = from_glib_full(out_container);
+            (index, from_glib(is_dir))
+        };
+        if index < 0 {
+            return Ok(None);
+        }
+        if !is_dir {
+            return Ok(Some(TreeChild::File(index)));
+        }
+        // tree_query_child() numbers the files first
+        let n_files = self.tree_get_contents()
+            .map_or(0, |contents| variant_utils::n_children(&variant_utils::child(&contents, 0)));
+        Ok(Some(TreeChild::Dir(index + n_files as i32)))
+    }
+}
+
+/// A child of a directory, as found by `RepoFile::tree_find_child()`.
+///
+/// The index is the one `RepoFile::tree_query_child()` takes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TreeChild {
+    File(i32),
+    Dir(i32),
+}
+
+impl TreeChild {
+    pub fn index(&self) -> i32 {
+        match *self {
+            TreeChild::File(index) | TreeChild::Dir(index) => index,
+        }
+    }
+
+    pub fn is_dir(&self) -> bool {
+        match *self {
+            TreeChild::File(_) => false,
+            TreeChild::Dir(_) => true,
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use std::ptr;
+
+use ffi;
+
+use SePolicy;
+
+impl SePolicy {
+    /// Resets the file creation context set by `setfscreatecon()`.
+    pub fn fscreatecon_cleanup() {
+        unsafe {
+            ffi::ostree_sepolicy_fscreatecon_cleanup(ptr::null_mut());
+        }
+    }
+}
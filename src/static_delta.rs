@@ -0,0 +1,245 @@
+//! Inspection of static delta superblocks.
+//!
+//! A superblock describes a static delta: the commits it converts between,
+//! the parts holding the delta payload and the objects that have to be
+//! fetched separately. Deltas written with the `filename` parameter of
+//! `ostree_repo_static_delta_generate()` consist of the superblock only, so
+//! they can be read here as well.
+
+use std::path::{Path, PathBuf};
+use std::ptr;
+
+use ffi;
+use gio_ffi;
+use glib::{Error, FileError, Variant, VariantType};
+use glib::translate::*;
+use glib_ffi;
+
+use ObjectType;
+use Repo;
+use variant_utils::*;
+
+const SUPERBLOCK_FORMAT: &str = "(a{sv}tayay(a{sv}aya(say)sstayay)aya(uayttay)a(yaytt))";
+
+/// Size of a packed object name in a part's object list: the object type
+/// byte followed by the binary checksum.
+const PACKED_OBJECT_NAME_LEN: usize = 1 + ffi::OSTREE_SHA256_DIGEST_LEN as usize;
+
+/// A part of a static delta holding a batch of objects.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StaticDeltaPart {
+    pub version: u32,
+    pub checksum: String,
+    pub size: u64,
+    pub uncompressed_size: u64,
+    pub n_objects: usize,
+}
+
+/// An object which is not part of the delta and is fetched on its own.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StaticDeltaFallback {
+    pub object_type: ObjectType,
+    pub checksum: String,
+    pub size: u64,
+    pub uncompressed_size: u64,
+}
+
+/// The decoded superblock of a static delta.
+#[derive(Clone, Debug)]
+pub struct StaticDeltaSuperblock {
+    /// Timestamp of the target commit.
+    pub timestamp: u64,
+    /// Source commit, `None` for a delta from scratch.
+    pub from: Option<String>,
+    /// Target commit.
+    pub to: String,
+    /// The `a{sv}` metadata of the delta.
+    pub metadata: Variant,
+    pub parts: Vec<StaticDeltaPart>,
+    pub fallback: Vec<StaticDeltaFallback>,
+}
+
+impl StaticDeltaSuperblock {
+    /// Loads the superblock of the delta between `from` and `to` in `repo`.
+    pub fn load<'a, P: Into<Option<&'a str>>>(repo: &Repo, from: P, to: &str) -> Result<StaticDeltaSuperblock, Error> {
+        let relpath = delta_relpath(from.into(), to)?;
+        let path: PathBuf = unsafe {
+            from_glib_full(gio_ffi::g_file_get_path(ffi::ostree_repo_get_path(repo.to_glib_none().0)))
+        };
+        StaticDeltaSuperblock::load_file(path.join(relpath))
+    }
+
+    /// Loads a superblock by delta name, as returned when listing the static
+    /// deltas of a repository: either `FROM-TO` or just `TO`.
+    pub fn load_by_name(repo: &Repo, name: &str) -> Result<StaticDeltaSuperblock, Error> {
+        match name.find('-') {
+            Some(i) => StaticDeltaSuperblock::load(repo, &name[..i], &name[i + 1..]),
+            None => StaticDeltaSuperblock::load(repo, None, name),
+        }
+    }
+
+    /// Loads a superblock from a file, e.g. a standalone delta.
+    pub fn load_file<P: AsRef<Path>>(path: P) -> Result<StaticDeltaSuperblock, Error> {
+        unsafe {
+            let mut contents = ptr::null_mut();
+            let mut length = 0;
+            let mut error = ptr::null_mut();
+            let _ = glib_ffi::g_file_get_contents(path.as_ref().to_glib_none().0, &mut contents, &mut length, &mut error);
+            if !error.is_null() {
+                return Err(from_glib_full(error));
+            }
+            StaticDeltaSuperblock::from_raw_data(contents, length)
+        }
+    }
+
+    /// Decodes a serialized superblock.
+    pub fn from_data(data: &[u8]) -> Result<StaticDeltaSuperblock, Error> {
+        unsafe {
+            // g_memdup() takes a 32-bit length
+            let contents = glib_ffi::g_malloc(data.len()) as *mut u8;
+            if !data.is_empty() {
+                ptr::copy_nonoverlapping(data.as_ptr(), contents, data.len());
+            }
+            StaticDeltaSuperblock::from_raw_data(contents, data.len())
+        }
+    }
+
+    /// Decodes `length` bytes at `contents`, taking ownership of the
+    /// `g_malloc()`ed buffer.
+    unsafe fn from_raw_data(contents: *mut u8, length: usize) -> Result<StaticDeltaSuperblock, Error> {
+        let type_ = VariantType::new(SUPERBLOCK_FORMAT).unwrap();
+        let superblock: Variant = from_glib_none(glib_ffi::g_variant_new_from_data(
+            type_.to_glib_none().0, contents as glib_ffi::gconstpointer, length, false.to_glib(),
+            Some(glib_ffi::g_free), contents as glib_ffi::gpointer));
+
+        let metadata = child(&superblock, 0);
+        let big_endian = match lookup_value(&metadata, "ostree.endianness", "y").map(|v| v.get::<u8>()) {
+            Some(Some(b'B')) => true,
+            Some(Some(b'l')) => false,
+            _ => cfg!(target_endian = "big"),
+        };
+        let to_u64 = |v: u64| if big_endian { u64::from_be(v) } else { u64::from_le(v) };
+        let to_u32 = |v: u32| if big_endian { u32::from_be(v) } else { u32::from_le(v) };
+
+        let timestamp = u64::from_be(child_u64(&superblock, 1));
+        let from = child_checksum(&superblock, 2)?;
+        let to = required_checksum(&superblock, 3, "target commit")?;
+
+        let entries = child(&superblock, 6);
+        let mut parts = Vec::new();
+        for i in 0..n_children(&entries) {
+            let entry = child(&entries, i);
+            parts.push(StaticDeltaPart {
+                version: to_u32(child_u32(&entry, 0)),
+                checksum: required_checksum(&entry, 1, "part")?,
+                size: to_u64(child_u64(&entry, 2)),
+                uncompressed_size: to_u64(child_u64(&entry, 3)),
+                n_objects: child_bytes(&entry, 4).len() / PACKED_OBJECT_NAME_LEN,
+            });
+        }
+
+        let fallbacks = child(&superblock, 7);
+        let mut fallback = Vec::new();
+        for i in 0..n_children(&fallbacks) {
+            let entry = child(&fallbacks, i);
+            fallback.push(StaticDeltaFallback {
+                object_type: object_type_from_byte(child_byte(&entry, 0))?,
+                checksum: required_checksum(&entry, 1, "fallback object")?,
+                size: to_u64(child_u64(&entry, 2)),
+                uncompressed_size: to_u64(child_u64(&entry, 3)),
+            });
+        }
+
+        Ok(StaticDeltaSuperblock {
+            timestamp,
+            from,
+            to,
+            metadata,
+            parts,
+            fallback,
+        })
+    }
+
+    /// The number of objects contained in all parts.
+    pub fn n_objects(&self) -> usize {
+        self.parts.iter().map(|p| p.n_objects).sum()
+    }
+
+    /// The compressed size of all parts and fallback objects, i.e. roughly
+    /// what a client has to download to apply the delta.
+    pub fn total_size(&self) -> u64 {
+        self.parts.iter().map(|p| p.size).sum::<u64>() +
+            self.fallback.iter().map(|f| f.size).sum::<u64>()
+    }
+
+    /// The uncompressed size of all parts and fallback objects.
+    pub fn total_uncompressed_size(&self) -> u64 {
+        self.parts.iter().map(|p| p.uncompressed_size).sum::<u64>() +
+            self.fallback.iter().map(|f| f.uncompressed_size).sum::<u64>()
+    }
+}
+
+/// Returns the path of a delta's superblock relative to the repository.
+///
+/// Mirrors `_ostree_get_relative_static_delta_path()`: commits are encoded
+/// with the modified base64 alphabet, and the first two characters form a
+/// fan-out directory.
+fn delta_relpath(from: Option<&str>, to: &str) -> Result<String, Error> {
+    let mut relpath = String::from("deltas/");
+    if let Some(from) = from {
+        let from = checksum_to_b64(from)?;
+        relpath.push_str(&from[..2]);
+        relpath.push('/');
+        relpath.push_str(&from[2..]);
+        relpath.push('-');
+    }
+    let to = checksum_to_b64(to)?;
+    relpath.push_str(&to[..2]);
+    if from.is_none() {
+        relpath.push('/');
+    }
+    relpath.push_str(&to[2..]);
+    relpath.push_str("/superblock");
+    Ok(relpath)
+}
+
+fn checksum_to_b64(checksum: &str) -> Result<String, Error> {
+    unsafe {
+        let mut error = ptr::null_mut();
+        let _ = ffi::ostree_validate_checksum_string(checksum.to_glib_none().0, &mut error);
+        if !error.is_null() {
+            return Err(from_glib_full(error));
+        }
+        let bytes = ffi::ostree_checksum_to_bytes(checksum.to_glib_none().0);
+        let ret = from_glib_full(ffi::ostree_checksum_b64_from_bytes(bytes));
+        glib_ffi::g_free(bytes as *mut _);
+        Ok(ret)
+    }
+}
+
+/// Like `child_checksum()`, failing if the checksum is empty.
+fn required_checksum(container: &Variant, index: usize, what: &str) -> Result<String, Error> {
+    match child_checksum(container, index)? {
+        Some(checksum) => Ok(checksum),
+        None => Err(Error::new(FileError::Inval, &format!("Static delta superblock has a {} without checksum", what))),
+    }
+}
+
+fn object_type_from_byte(objtype: u8) -> Result<ObjectType, Error> {
+    unsafe {
+        let mut error = ptr::null_mut();
+        let _ = ffi::ostree_validate_structureof_objtype(objtype, &mut error);
+        if !error.is_null() {
+            return Err(from_glib_full(error));
+        }
+    }
+    Ok(match objtype as i32 {
+        x if x == ffi::OSTREE_OBJECT_TYPE_FILE as i32 => ObjectType::File,
+        x if x == ffi::OSTREE_OBJECT_TYPE_DIR_TREE as i32 => ObjectType::DirTree,
+        x if x == ffi::OSTREE_OBJECT_TYPE_DIR_META as i32 => ObjectType::DirMeta,
+        x if x == ffi::OSTREE_OBJECT_TYPE_COMMIT as i32 => ObjectType::Commit,
+        x if x == ffi::OSTREE_OBJECT_TYPE_TOMBSTONE_COMMIT as i32 => ObjectType::TombstoneCommit,
+        x if x == ffi::OSTREE_OBJECT_TYPE_COMMIT_META as i32 => ObjectType::CommitMeta,
+        _ => return Err(Error::new(FileError::Inval, &format!("Unsupported object type {} in static delta", objtype))),
+    })
+}
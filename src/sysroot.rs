@@ -0,0 +1,53 @@
+use std::ptr;
+
+use ffi;
+use gio;
+use glib::Error;
+use glib::translate::*;
+use glib_ffi;
+
+use Deployment;
+use KernelArgs;
+use Sysroot;
+use ptr_array;
+
+impl Sysroot {
+    /// Returns the deployments of the loaded sysroot, in boot order.
+    pub fn get_deployments(&self) -> Vec<Deployment> {
+        unsafe {
+            let deployments = ffi::ostree_sysroot_get_deployments(self.to_glib_none().0);
+            if deployments.is_null() {
+                return Vec::new();
+            }
+            ptr_array::take(deployments, |deployment| from_glib_none(deployment as *mut ffi::OstreeDeployment))
+        }
+    }
+
+    /// Replaces the deployment list of the sysroot with `new_deployments`,
+    /// in boot order, and writes the new boot configuration.
+    pub fn write_deployments(&self, new_deployments: &[Deployment], cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        unsafe {
+            let deployments = ptr_array::from_slice::<_, ffi::OstreeDeployment>(new_deployments);
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_write_deployments(self.to_glib_none().0, deployments, cancellable.to_glib_none().0, &mut error);
+            glib_ffi::g_ptr_array_unref(deployments);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    /// Replaces the kernel arguments of `deployment`, which must be one of
+    /// the current deployments.
+    pub fn deployment_set_kargs(&self, deployment: &Deployment, new_kargs: Vec<String>, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        let new_kargs: Vec<&str> = new_kargs.iter().map(String::as_str).collect();
+        unsafe {
+            let mut error = ptr::null_mut();
+            let _ = ffi::ostree_sysroot_deployment_set_kargs(self.to_glib_none().0, deployment.to_glib_none().0, new_kargs[..].to_glib_none().0, cancellable.to_glib_none().0, &mut error);
+            if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) }
+        }
+    }
+
+    /// Like `deployment_set_kargs()`, taking parsed arguments.
+    pub fn deployment_set_kernel_args(&self, deployment: &Deployment, args: &KernelArgs, cancellable: Option<&gio::Cancellable>) -> Result<(), Error> {
+        self.deployment_set_kargs(deployment, args.as_slice().to_vec(), cancellable)
+    }
+}
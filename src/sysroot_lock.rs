@@ -0,0 +1,123 @@
+use std::future::Future;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::ptr;
+use std::task::{Context, Poll};
+
+use ffi;
+use gio;
+use gio_ffi;
+use glib::Error;
+use glib::translate::*;
+use glib_ffi;
+use gobject_ffi;
+
+use Sysroot;
+use future_utils::{Abandoned, FutureState};
+
+/// Holds the sysroot lock, releasing it when dropped.
+pub struct SysrootLock {
+    sysroot: Sysroot,
+}
+
+impl SysrootLock {
+    /// Takes ownership of the lock currently held on `sysroot`.
+    fn new(sysroot: Sysroot) -> SysrootLock {
+        SysrootLock { sysroot }
+    }
+}
+
+impl Deref for SysrootLock {
+    type Target = Sysroot;
+
+    fn deref(&self) -> &Sysroot {
+        &self.sysroot
+    }
+}
+
+impl Drop for SysrootLock {
+    fn drop(&mut self) {
+        self.sysroot.unlock();
+    }
+}
+
+/// Future returned by `Sysroot::lock_future()`.
+///
+/// The lock is acquired in a worker thread and completion is dispatched
+/// through the thread-default main context, which must be iterated for the
+/// future to make progress.
+pub struct LockFuture {
+    sysroot: Sysroot,
+    cancellable: gio::Cancellable,
+    state: FutureState<Result<(), Error>>,
+}
+
+impl Future for LockFuture {
+    type Output = Result<SysrootLock, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<SysrootLock, Error>> {
+        let this = self.get_mut();
+        let poll = this.state.poll(cx, || {
+            let state = this.state.clone();
+            let sysroot = this.sysroot.clone();
+            this.sysroot.lock_async(Some(&this.cancellable), move |result| {
+                // Cancelling doesn't stop the worker thread from taking the
+                // lock, it only makes the result report the cancellation, so
+                // the lock may be held whatever the result of an abandoned
+                // attempt. Unlocking a sysroot that isn't locked is a no-op.
+                if state.complete(result).is_err() {
+                    sysroot.unlock();
+                }
+            });
+        });
+        match poll {
+            Poll::Ready(Ok(())) => Poll::Ready(Ok(SysrootLock::new(this.sysroot.clone()))),
+            Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl Drop for LockFuture {
+    fn drop(&mut self) {
+        match self.state.abandon() {
+            // Acquired but never handed out.
+            Abandoned::Finished(Ok(())) => self.sysroot.unlock(),
+            Abandoned::Running => self.cancellable.cancel(),
+            Abandoned::Finished(Err(_)) | Abandoned::Idle => (),
+        }
+    }
+}
+
+impl Sysroot {
+    /// Acquires the sysroot lock without blocking, invoking `callback` from
+    /// the thread-default main context once it is held or has failed.
+    pub fn lock_async<F: FnOnce(Result<(), Error>) + 'static>(&self, cancellable: Option<&gio::Cancellable>, callback: F) {
+        let user_data: Box<Box<F>> = Box::new(Box::new(callback));
+        unsafe {
+            ffi::ostree_sysroot_lock_async(self.to_glib_none().0, cancellable.to_glib_none().0, Some(lock_async_trampoline::<F>), Box::into_raw(user_data) as glib_ffi::gpointer);
+        }
+    }
+
+    /// Returns a future resolving to a guard on the sysroot lock, for use
+    /// with a GLib main context based executor.
+    ///
+    /// Dropping the future before it completes abandons the attempt; a lock
+    /// acquired in the meantime is released right away.
+    pub fn lock_future(&self) -> LockFuture {
+        LockFuture {
+            sysroot: self.clone(),
+            cancellable: gio::Cancellable::new(),
+            state: FutureState::new(),
+        }
+    }
+}
+
+unsafe extern "C" fn lock_async_trampoline<F: FnOnce(Result<(), Error>) + 'static>(source_object: *mut gobject_ffi::GObject, res: *mut gio_ffi::GAsyncResult, user_data: glib_ffi::gpointer) {
+    callback_guard!();
+    let callback: Box<Box<F>> = Box::from_raw(user_data as *mut _);
+    let mut error = ptr::null_mut();
+    let _ = ffi::ostree_sysroot_lock_finish(source_object as *mut _, res, &mut error);
+    let result = if error.is_null() { Ok(()) } else { Err(from_glib_full(error)) };
+    callback(result);
+}
@@ -0,0 +1,130 @@
+//! Helpers for decoding and building `GVariant` containers.
+//!
+//! `glib::Variant` only supports basic types, so the structured OSTree
+//! formats are taken apart through the FFI here.
+
+use std::ptr;
+use std::slice;
+
+use ffi;
+use glib::{Error, Variant, VariantTy, VariantType};
+use glib::translate::*;
+use glib_ffi;
+
+use Xattrs;
+
+pub fn n_children(container: &Variant) -> usize {
+    unsafe {
+        glib_ffi::g_variant_n_children(container.to_glib_none().0)
+    }
+}
+
+pub fn child(container: &Variant, index: usize) -> Variant {
+    unsafe {
+        from_glib_full(glib_ffi::g_variant_get_child_value(container.to_glib_none().0, index))
+    }
+}
+
+pub fn child_bool(container: &Variant, index: usize) -> bool {
+    unsafe {
+        from_glib(glib_ffi::g_variant_get_boolean(child(container, index).to_glib_none().0))
+    }
+}
+
+pub fn child_byte(container: &Variant, index: usize) -> u8 {
+    unsafe {
+        glib_ffi::g_variant_get_byte(child(container, index).to_glib_none().0)
+    }
+}
+
+pub fn child_u32(container: &Variant, index: usize) -> u32 {
+    unsafe {
+        glib_ffi::g_variant_get_uint32(child(container, index).to_glib_none().0)
+    }
+}
+
+pub fn child_i64(container: &Variant, index: usize) -> i64 {
+    unsafe {
+        glib_ffi::g_variant_get_int64(child(container, index).to_glib_none().0)
+    }
+}
+
+pub fn child_u64(container: &Variant, index: usize) -> u64 {
+    unsafe {
+        glib_ffi::g_variant_get_uint64(child(container, index).to_glib_none().0)
+    }
+}
+
+pub fn child_str(container: &Variant, index: usize) -> String {
+    child(container, index).get_str().unwrap_or_default().to_owned()
+}
+
+/// Returns the contents of an `ay` child.
+pub fn child_bytes(container: &Variant, index: usize) -> Vec<u8> {
+    let child = child(container, index);
+    unsafe {
+        let mut len = 0;
+        let data = glib_ffi::g_variant_get_fixed_array(child.to_glib_none().0, &mut len, 1);
+        if len == 0 {
+            Vec::new()
+        } else {
+            slice::from_raw_parts(data as *const u8, len).to_vec()
+        }
+    }
+}
+
+/// Returns the hex form of a binary checksum child (`ay`), or `None` if it
+/// is empty.
+pub fn child_checksum(container: &Variant, index: usize) -> Result<Option<String>, Error> {
+    let child = child(container, index);
+    if n_children(&child) == 0 {
+        return Ok(None);
+    }
+    unsafe {
+        let mut error = ptr::null_mut();
+        let _ = ffi::ostree_validate_structureof_csum_v(child.to_glib_none().0, &mut error);
+        if error.is_null() {
+            Ok(Some(from_glib_full(ffi::ostree_checksum_from_bytes_v(child.to_glib_none().0))))
+        } else {
+            Err(from_glib_full(error))
+        }
+    }
+}
+
+/// Looks up `key` in an `a{sv}` dictionary, if it has the type `type_`.
+pub fn lookup_value(dict: &Variant, key: &str, type_: &str) -> Option<Variant> {
+    let type_ = VariantType::new(type_).unwrap();
+    unsafe {
+        from_glib_full(glib_ffi::g_variant_lookup_value(dict.to_glib_none().0, key.to_glib_none().0,
+                                                        type_.to_glib_none().0))
+    }
+}
+
+/// Decodes an `a(ayay)` list of extended attributes.
+pub fn xattrs(xattrs: &Variant) -> Xattrs {
+    (0..n_children(xattrs)).map(|i| {
+        let xattr = child(xattrs, i);
+        (child_bytes(&xattr, 0), child_bytes(&xattr, 1))
+    }).collect()
+}
+
+/// Encodes extended attributes as an `a(ayay)` list.
+pub fn new_xattrs(xattrs: &[(Vec<u8>, Vec<u8>)]) -> Variant {
+    let type_ = VariantTy::new("a(ayay)").unwrap();
+    unsafe {
+        let builder = glib_ffi::g_variant_builder_new(type_.as_ptr());
+        for (name, value) in xattrs {
+            let mut children = [new_bytes(name), new_bytes(value)];
+            glib_ffi::g_variant_builder_add_value(builder, glib_ffi::g_variant_new_tuple(children.as_mut_ptr(), children.len()));
+        }
+        let ret = glib_ffi::g_variant_builder_end(builder);
+        glib_ffi::g_variant_builder_unref(builder);
+        from_glib_none(ret)
+    }
+}
+
+/// Returns a new floating `ay` variant holding a copy of `data`.
+unsafe fn new_bytes(data: &[u8]) -> *mut glib_ffi::GVariant {
+    let byte = VariantTy::new("y").unwrap();
+    glib_ffi::g_variant_new_fixed_array(byte.as_ptr(), data.as_ptr() as glib_ffi::gconstpointer, data.len(), 1)
+}
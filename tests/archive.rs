@@ -0,0 +1,70 @@
+#![cfg(feature = "libarchive")]
+
+extern crate glib;
+extern crate ostree;
+
+mod common;
+
+use std::fs;
+use std::io::Read;
+
+use glib::object::Downcast;
+use ostree::{gio, ExportArchiveOptions, ImportArchiveOptions, MutableTree, Repo, RepoFile};
+use ostree::gio::FileExt;
+
+use common::TempDir;
+
+fn read_child(root: &RepoFile, path: &str) -> String {
+    let mut contents = String::new();
+    root.get_child(path).unwrap().read(None).unwrap().read_to_string(&mut contents).unwrap();
+    contents
+}
+
+#[test]
+fn import_commit_and_export_a_tar() {
+    let dir = TempDir::new();
+    let tree = dir.path().join("tree");
+    common::write_tree(&tree, &[
+        ("etc/config", b"setting=1\n"),
+        ("usr/bin/tool", b"#!/bin/sh\n"),
+    ]);
+    let tar = dir.path().join("input.tar");
+    common::run("tar", &["-cf", tar.to_str().unwrap(), "-C", tree.to_str().unwrap(), "etc", "usr"]);
+
+    let repo_path = dir.path().join("repo");
+    common::init_repo(&repo_path);
+    let repo = Repo::new(&gio::File::new_for_path(&repo_path));
+    repo.open(None).unwrap();
+
+    repo.prepare_transaction(None).unwrap();
+    let mtree = MutableTree::new();
+    let options = ImportArchiveOptions {
+        autocreate_parents: true,
+        use_ostree_convention: true,
+        ..Default::default()
+    };
+    repo.import_archive_to_mtree(&options, &tar, &mtree, None, None).unwrap();
+    let root: RepoFile = repo.write_mtree(&mtree, None).unwrap().downcast().unwrap();
+    let commit = repo.write_commit(None, "Imported from tar", None, None, &root, None).unwrap();
+    repo.commit_transaction(None).unwrap();
+
+    let (root, checksum) = repo.read_commit(&commit, None).unwrap();
+    assert_eq!(checksum, commit);
+    let root: RepoFile = root.downcast().unwrap();
+    root.ensure_resolved().unwrap();
+    // The OSTree convention moves /etc below /usr
+    assert_eq!(root.tree_find_child("etc").unwrap(), None);
+    assert_eq!(read_child(&root, "usr/etc/config"), "setting=1\n");
+    assert_eq!(read_child(&root, "usr/bin/tool"), "#!/bin/sh\n");
+
+    let mut exported = Vec::new();
+    repo.export_tree_to_writer(&ExportArchiveOptions::default(), &root, &mut exported, None).unwrap();
+    let output = dir.path().join("output.tar");
+    fs::write(&output, exported).unwrap();
+    let extracted = dir.path().join("extracted");
+    fs::create_dir(&extracted).unwrap();
+    common::run("tar", &["-xf", output.to_str().unwrap(), "-C", extracted.to_str().unwrap()]);
+    assert_eq!(fs::read(extracted.join("usr/etc/config")).unwrap(), b"setting=1\n");
+    assert_eq!(fs::read(extracted.join("usr/bin/tool")).unwrap(), b"#!/bin/sh\n");
+    assert!(!extracted.join("etc").exists());
+}
@@ -0,0 +1,53 @@
+extern crate ostree;
+
+mod common;
+
+use std::fs::File;
+use std::os::unix::io::AsRawFd;
+
+use common::TempDir;
+use ostree::{gio, BootconfigParser, KernelArgs};
+
+const ENTRY: &[u8] = b"\
+title Fedora 28.20180515.1 (ostree)
+version 1
+linux /ostree/fedora-0123/vmlinuz-4.16.3
+initrd /ostree/fedora-0123/initramfs-4.16.3.img
+options root=/dev/mapper/root rw quiet ostree=/ostree/boot.1/fedora/0123/0
+";
+
+#[test]
+fn parse_set_write() {
+    let dir = TempDir::new();
+    common::write_tree(dir.path(), &[("loader/entries/ostree-1-fedora.conf", ENTRY)]);
+    let entry = gio::File::new_for_path(dir.path().join("loader/entries/ostree-1-fedora.conf"));
+
+    let parser = BootconfigParser::new();
+    parser.parse(&entry, None).unwrap();
+    assert_eq!(parser.get("title").unwrap(), "Fedora 28.20180515.1 (ostree)");
+    assert_eq!(parser.get("version").unwrap(), "1");
+    assert_eq!(parser.get("missing"), None);
+    let mut options = parser.options().unwrap();
+    assert!(options.contains("ostree=/ostree/boot.1/fedora/0123/0"));
+
+    options.delete("quiet");
+    options.append("console=ttyS0");
+    parser.set_options(&options);
+    parser.set("version", "2");
+    let output = gio::File::new_for_path(dir.path().join("loader/entries/ostree-2-fedora.conf"));
+    parser.write(&output, None).unwrap();
+
+    let reparsed = BootconfigParser::new();
+    let boot = File::open(dir.path()).unwrap();
+    reparsed.parse_at(boot.as_raw_fd(), "loader/entries/ostree-2-fedora.conf", None).unwrap();
+    assert_eq!(reparsed.get("title").unwrap(), "Fedora 28.20180515.1 (ostree)");
+    assert_eq!(reparsed.get("version").unwrap(), "2");
+    assert_eq!(reparsed.get("linux").unwrap(), "/ostree/fedora-0123/vmlinuz-4.16.3");
+    assert_eq!(reparsed.options().unwrap(),
+               KernelArgs::parse("root=/dev/mapper/root rw ostree=/ostree/boot.1/fedora/0123/0 console=ttyS0"));
+
+    // The copy is independent of the original
+    let copy = reparsed.clone_parser();
+    copy.set("version", "3");
+    assert_eq!(reparsed.get("version").unwrap(), "2");
+}
@@ -0,0 +1,74 @@
+extern crate ostree;
+
+mod common;
+
+use std::fs;
+use std::io::{self, Read};
+
+use ostree::gio::{self, FileExt};
+use ostree::{ChecksumInputStream, ChecksumReader, ObjectType};
+
+use common::{block_on, TempDir};
+
+#[test]
+fn checksum_file_future_matches_checksum_file() {
+    let dir = TempDir::new();
+    let path = dir.path().join("file");
+    fs::write(&path, b"some content\n").unwrap();
+
+    let expected = ostree::checksum_file(&path, ObjectType::File, None).unwrap();
+    let checksum = block_on(ostree::checksum_file_future(&path, ObjectType::File, 0)).unwrap();
+    assert_eq!(checksum.to_string(), expected.to_string());
+}
+
+#[test]
+fn checksum_file_from_read_matches_checksum_file() {
+    let dir = TempDir::new();
+    let path = dir.path().join("file");
+    fs::write(&path, b"some content\n").unwrap();
+
+    let file_info = gio::File::new_for_path(&path)
+        .query_info("standard::*,unix::*", gio::FILE_QUERY_INFO_NOFOLLOW_SYMLINKS, None)
+        .unwrap();
+    let expected = ostree::checksum_file(&path, ObjectType::File, None).unwrap();
+    let checksum = ostree::checksum_file_from_read(&file_info, &[], fs::File::open(&path).unwrap(), ObjectType::File, None).unwrap();
+    assert_eq!(checksum.to_string(), expected.to_string());
+}
+
+#[test]
+fn dropping_a_started_checksum_file_future_cancels_it() {
+    let dir = TempDir::new();
+    let path = dir.path().join("file");
+    fs::write(&path, b"some content\n").unwrap();
+
+    let mut future = Box::pin(ostree::checksum_file_future(&path, ObjectType::File, 0));
+    assert!(common::poll_once(future.as_mut()).is_pending());
+    drop(future);
+    // The cancelled operation still completes, into the abandoned state.
+    common::iterate_main_context();
+}
+
+#[test]
+fn checksum_reader_matches_checksum_file() {
+    let dir = TempDir::new();
+    let path = dir.path().join("file");
+    let data: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+    fs::write(&path, &data).unwrap();
+
+    // Metadata objects are checksummed as they are, without a file header
+    let expected = ostree::checksum_file(&path, ObjectType::DirMeta, None).unwrap();
+    let mut reader = ChecksumReader::new(fs::File::open(&path).unwrap());
+    let mut buf = [0; 1000];
+    reader.read_exact(&mut buf).unwrap();
+    assert_ne!(reader.checksum(), expected);
+    io::copy(&mut reader, &mut io::sink()).unwrap();
+    assert_eq!(reader.checksum(), expected);
+    // Reading past the end changes nothing
+    assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    let (_, checksum) = reader.into_inner();
+    assert_eq!(checksum, expected);
+
+    let mut stream = ChecksumInputStream::new(&gio::InputStream::from_data(&data));
+    io::copy(&mut stream, &mut io::sink()).unwrap();
+    assert_eq!(stream.checksum(), Some(expected));
+}
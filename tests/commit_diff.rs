@@ -0,0 +1,79 @@
+extern crate ostree;
+
+mod common;
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+
+use common::TempDir;
+use ostree::{DiffSummary, PathChangeKind, Repo};
+
+#[test]
+fn diff_commits() {
+    let dir = TempDir::new();
+    let repo_path = dir.path().join("repo");
+    common::init_repo(&repo_path);
+
+    let old = dir.path().join("old");
+    common::write_tree(&old, &[
+        ("a.txt", b"hello"),
+        ("b.txt", b"remove me"),
+        ("c", b"x"),
+        ("d/e", b"e"),
+        ("m.sh", b"#!/bin/sh\n"),
+    ]);
+    fs::set_permissions(old.join("m.sh"), fs::Permissions::from_mode(0o644)).unwrap();
+    let from = common::commit(&repo_path, "test", &old);
+
+    let new = dir.path().join("new");
+    common::write_tree(&new, &[
+        ("a.txt", b"hello world"),
+        ("c/f", b"ff"),
+        ("d", b"dd"),
+        ("m.sh", b"#!/bin/sh\n"),
+        ("new.txt", b"new"),
+    ]);
+    fs::set_permissions(new.join("m.sh"), fs::Permissions::from_mode(0o755)).unwrap();
+    let to = common::commit(&repo_path, "test", &new);
+
+    let repo = Repo::new(&ostree::gio::File::new_for_path(&repo_path));
+    repo.open(None).unwrap();
+    let diff = repo.diff_commits(&from, "test", None).unwrap();
+    assert_eq!(repo.read_commit("test", None).unwrap().1, to);
+
+    let changes: Vec<_> = diff.changes.iter()
+        .map(|change| (change.path.clone(), change.kind, change.old_size, change.new_size))
+        .collect();
+    let expected = vec![
+        ("/a.txt", PathChangeKind::ContentModified, Some(5), Some(11)),
+        ("/b.txt", PathChangeKind::Removed, Some(9), None),
+        ("/c", PathChangeKind::Removed, Some(1), None),
+        ("/c", PathChangeKind::Added, None, None),
+        ("/c/f", PathChangeKind::Added, None, Some(2)),
+        ("/d", PathChangeKind::Removed, None, None),
+        ("/d", PathChangeKind::Added, None, Some(2)),
+        ("/d/e", PathChangeKind::Removed, Some(1), None),
+        ("/m.sh", PathChangeKind::MetadataModified, Some(10), Some(10)),
+        ("/new.txt", PathChangeKind::Added, None, Some(3)),
+    ];
+    let expected: Vec<_> = expected.into_iter()
+        .map(|(path, kind, old_size, new_size)| (PathBuf::from(path), kind, old_size, new_size))
+        .collect();
+    assert_eq!(changes, expected);
+
+    for change in &diff.changes {
+        assert_eq!(change.old_checksum.is_some(), change.kind != PathChangeKind::Added, "{:?}", change);
+        assert_eq!(change.new_checksum.is_some(), change.kind != PathChangeKind::Removed, "{:?}", change);
+    }
+
+    assert_eq!(diff.summary(), DiffSummary {
+        added: 4,
+        removed: 4,
+        content_modified: 1,
+        metadata_modified: 1,
+        added_size: 11 + 2 + 2 + 3,
+        removed_size: 5 + 9 + 1 + 1,
+    });
+    assert_eq!(diff.summary().size_delta(), 2);
+}
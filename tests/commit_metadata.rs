@@ -0,0 +1,68 @@
+extern crate glib;
+extern crate ostree;
+
+mod common;
+
+use glib::object::Downcast;
+use ostree::{gio, CommitMetadata, Repo, RepoFile};
+
+use common::TempDir;
+
+fn metadata() -> CommitMetadata {
+    let mut metadata = CommitMetadata::new();
+    metadata.set_version("1.2.3");
+    metadata.set_ref_binding(&["os/stable", "os/testing"]);
+    metadata.set_endoflife("Moved to os/next");
+    metadata
+}
+
+/// Commits a one-file tree with the CLI, returning the opened repository
+/// and the commit.
+fn repo_with_commit(dir: &TempDir) -> (Repo, String) {
+    let repo_path = dir.path().join("repo");
+    common::init_repo(&repo_path);
+    let tree = dir.path().join("tree");
+    common::write_tree(&tree, &[("file", b"content")]);
+    let commit = common::commit(&repo_path, "test", &tree);
+    let repo = Repo::new(&gio::File::new_for_path(&repo_path));
+    repo.open(None).unwrap();
+    (repo, commit)
+}
+
+#[test]
+fn detached_metadata_round_trip() {
+    let dir = TempDir::new();
+    let (repo, commit) = repo_with_commit(&dir);
+    assert!(repo.commit_detached_metadata(&commit, None).unwrap().is_empty());
+
+    repo.set_commit_detached_metadata(&commit, &metadata(), None).unwrap();
+    let read = repo.commit_detached_metadata(&commit, None).unwrap();
+    assert_eq!(read, metadata());
+    assert_eq!(read.version(), Some("1.2.3"));
+    assert_eq!(read.ref_binding(), Some(vec!["os/stable".to_owned(), "os/testing".to_owned()]));
+    assert_eq!(read.endoflife(), Some("Moved to os/next"));
+    assert_eq!(read.endoflife_rebase(), None);
+
+    // An empty map removes the detached metadata
+    repo.set_commit_detached_metadata(&commit, &CommitMetadata::new(), None).unwrap();
+    assert!(repo.read_commit_detached_metadata(&commit, None).unwrap().is_none());
+    assert!(repo.commit_detached_metadata(&commit, None).unwrap().is_empty());
+}
+
+#[test]
+fn commit_metadata_is_written_with_the_commit() {
+    let dir = TempDir::new();
+    let (repo, commit) = repo_with_commit(&dir);
+    let (root, _) = repo.read_commit(&commit, None).unwrap();
+    let root: RepoFile = root.downcast().unwrap();
+
+    repo.prepare_transaction(None).unwrap();
+    let commit = repo.write_commit(&commit[..], "With metadata", None, Some(&metadata().to_variant()), &root, None).unwrap();
+    repo.commit_transaction(None).unwrap();
+
+    let repo_arg = format!("--repo={}", dir.path().join("repo").display());
+    let show = |key: &str| common::run("ostree", &["show", &repo_arg, &format!("--print-metadata-key={}", key), &commit]);
+    assert_eq!(show("version"), "'1.2.3'");
+    assert_eq!(show("ostree.ref-binding"), "['os/stable', 'os/testing']");
+    assert_eq!(show("ostree.endoflife"), "'Moved to os/next'");
+}
@@ -0,0 +1,150 @@
+//! Helpers shared by the integration tests.
+
+#![allow(dead_code)]
+
+extern crate glib_sys;
+
+use std::env;
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::process::Command;
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+use ostree::gio::FileExt;
+use ostree::{gio, Deployment, Sysroot, SYSROOT_SIMPLE_WRITE_DEPLOYMENT_FLAGS_NONE};
+
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A directory under the system temp dir, removed when dropped.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new() -> TempDir {
+        let name = format!("ostree-rs-test-{}-{}", std::process::id(), COUNTER.fetch_add(1, Ordering::SeqCst));
+        let path = env::temp_dir().join(name);
+        fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+
+    pub fn file(&self) -> gio::File {
+        gio::File::new_for_path(&self.0)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Runs `program` with `args`, returning its trimmed stdout.
+pub fn run(program: &str, args: &[&str]) -> String {
+    let output = Command::new(program).args(args).output()
+        .unwrap_or_else(|err| panic!("Failed to run {}: {}", program, err));
+    assert!(output.status.success(), "{} {:?} failed: {}", program, args, String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap().trim().to_owned()
+}
+
+/// Creates the files `(path, contents)` below `root`, with their parents.
+pub fn write_tree(root: &Path, files: &[(&str, &[u8])]) {
+    for &(path, contents) in files {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+}
+
+/// Creates a `bare-user` repository at `path` with the `ostree` CLI.
+pub fn init_repo(path: &Path) {
+    run("ostree", &["init", "--mode=bare-user", &format!("--repo={}", path.display())]);
+}
+
+/// Commits the directory `tree` to `branch` with the `ostree` CLI, returning
+/// the commit checksum.
+pub fn commit(repo: &Path, branch: &str, tree: &Path) -> String {
+    run("ostree", &["commit", &format!("--repo={}", repo.display()), &format!("--branch={}", branch),
+                    "--subject=test", &format!("--tree=dir={}", tree.display())])
+}
+
+/// The OS name `deploy_commit()` deploys as.
+pub const OSNAME: &str = "testos";
+
+/// A sysroot under `dir`, initialized and loaded. Deployments are left
+/// mutable, as making them immutable needs root.
+pub fn new_sysroot(dir: &TempDir) -> Sysroot {
+    env::set_var("OSTREE_SYSROOT_DEBUG", "mutable-deployments");
+    let sysroot = Sysroot::new(Some(&dir.file()));
+    sysroot.ensure_initialized(None).unwrap();
+    sysroot.load(None).unwrap();
+    sysroot
+}
+
+/// Commits a minimal bootable tree to `testos/stable` in the repository of
+/// `sysroot`, returning the commit checksum. `OSNAME` must be initialized.
+pub fn commit_os_tree(sysroot: &Sysroot, version: &str) -> String {
+    let tree = TempDir::new();
+    write_tree(tree.path(), &[
+        ("usr/lib/modules/4.16.3/vmlinuz", b"kernel"),
+        ("usr/lib/modules/4.16.3/initramfs.img", b"initramfs"),
+        ("usr/etc/os-release", format!("NAME=testos\nVERSION={}\n", version).as_bytes()),
+        ("usr/bin/true", b""),
+    ]);
+    let repo = sysroot.get_path().unwrap().get_path().unwrap().join("ostree/repo");
+    run("ostree", &["commit", &format!("--repo={}", repo.display()), "--branch=testos/stable",
+                    &format!("--subject={}", version), &format!("--add-metadata-string=version={}", version),
+                    &format!("--tree=dir={}", tree.path().display())])
+}
+
+/// Deploys `rev` with the kernel arguments `args`, makes it the only
+/// deployment of `OSNAME` and reloads the sysroot.
+pub fn deploy_commit(sysroot: &Sysroot, rev: &str, args: &[&str]) -> Deployment {
+    let origin = sysroot.new_origin("testos/stable").unwrap();
+    let deployment = sysroot.deploy_tree(OSNAME, rev, Some(&origin.to_key_file()), None, Some(args), None).unwrap();
+    sysroot.simple_write_deployment(OSNAME, &deployment, None, SYSROOT_SIMPLE_WRITE_DEPLOYMENT_FLAGS_NONE, None).unwrap();
+    sysroot.load(None).unwrap();
+    deployment
+}
+
+fn noop_waker() -> Waker {
+    fn raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker { raw_waker() }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(ptr::null(), &VTABLE)
+    }
+
+    unsafe { Waker::from_raw(raw_waker()) }
+}
+
+/// Polls `future` once with a waker that does nothing.
+pub fn poll_once<F: Future>(future: Pin<&mut F>) -> Poll<F::Output> {
+    future.poll(&mut Context::from_waker(&noop_waker()))
+}
+
+/// Blocks until the thread-default main context dispatched something.
+pub fn iterate_main_context() {
+    unsafe {
+        glib_sys::g_main_context_iteration(ptr::null_mut(), glib_sys::GTRUE);
+    }
+}
+
+/// Polls `future` to completion, blocking on the thread-default main context
+/// between polls. GIO callbacks only run from a main context iteration, after
+/// which the future is polled again anyway, so no waker is needed.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = Box::pin(future);
+    loop {
+        if let Poll::Ready(output) = poll_once(future.as_mut()) {
+            return output;
+        }
+        iterate_main_context();
+    }
+}
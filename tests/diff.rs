@@ -0,0 +1,96 @@
+extern crate ostree;
+
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use common::TempDir;
+use ostree::gio::{self, FileExt};
+use ostree::{diff_dirs, Repo, DIFF_FLAGS_IGNORE_XATTRS, DIFF_FLAGS_NONE};
+
+fn checkout(repo: &Path, rev: &str, dest: &Path) -> gio::File {
+    common::run("ostree", &["checkout", &format!("--repo={}", repo.display()), "--user-mode", rev, &dest.display().to_string()]);
+    gio::File::new_for_path(dest)
+}
+
+fn relative_paths(root: &Path, files: &[gio::File]) -> Vec<PathBuf> {
+    let mut paths: Vec<_> = files.iter()
+        .map(|file| file.get_path().unwrap().strip_prefix(root).unwrap().to_owned())
+        .collect();
+    paths.sort();
+    paths
+}
+
+#[test]
+fn diff_checkouts() {
+    let dir = TempDir::new();
+    let repo_path = dir.path().join("repo");
+    common::init_repo(&repo_path);
+
+    let old = dir.path().join("old");
+    common::write_tree(&old, &[
+        ("a.txt", b"hello"),
+        ("b.txt", b"remove me"),
+        ("d/e", b"e"),
+        ("same.txt", b"same"),
+    ]);
+    let from = common::commit(&repo_path, "test", &old);
+    let new = dir.path().join("new");
+    common::write_tree(&new, &[
+        ("a.txt", b"hello world"),
+        ("d/e", b"e2"),
+        ("d/f", b"f"),
+        ("new.txt", b"new"),
+        ("same.txt", b"same"),
+    ]);
+    let to = common::commit(&repo_path, "test", &new);
+
+    let from_dir = dir.path().join("from");
+    let to_dir = dir.path().join("to");
+    let a = checkout(&repo_path, &from, &from_dir);
+    let b = checkout(&repo_path, &to, &to_dir);
+
+    let diff = diff_dirs(DIFF_FLAGS_NONE, &a, &b, None).unwrap();
+    assert!(!diff.is_empty());
+    let sources: Vec<_> = diff.modified.iter().map(|item| item.src()).collect();
+    let targets: Vec<_> = diff.modified.iter().map(|item| item.target()).collect();
+    assert_eq!(relative_paths(&from_dir, &sources), [Path::new("a.txt"), Path::new("d/e")]);
+    assert_eq!(relative_paths(&to_dir, &targets), [Path::new("a.txt"), Path::new("d/e")]);
+    for item in &diff.modified {
+        assert!(item.src_checksum().is_some());
+        assert_ne!(item.src_checksum(), item.target_checksum());
+        assert!(item.src_info().get_size() < item.target_info().get_size());
+    }
+    assert_eq!(relative_paths(&from_dir, &diff.removed), [Path::new("b.txt")]);
+    assert_eq!(relative_paths(&to_dir, &diff.added), [Path::new("d/f"), Path::new("new.txt")]);
+    diff.print(&a, &b);
+
+    // Nothing changed in the other direction but the roles
+    let reverse = diff_dirs(DIFF_FLAGS_NONE, &b, &a, None).unwrap();
+    assert_eq!(reverse.modified.len(), 2);
+    assert_eq!(relative_paths(&to_dir, &reverse.removed), relative_paths(&to_dir, &diff.added));
+    assert_eq!(relative_paths(&from_dir, &reverse.added), relative_paths(&from_dir, &diff.removed));
+}
+
+#[test]
+fn diff_identical_trees() {
+    let dir = TempDir::new();
+    let repo_path = dir.path().join("repo");
+    common::init_repo(&repo_path);
+    let tree = dir.path().join("tree");
+    common::write_tree(&tree, &[("a.txt", b"hello"), ("d/e", b"e")]);
+    let rev = common::commit(&repo_path, "test", &tree);
+
+    let a = checkout(&repo_path, &rev, &dir.path().join("a"));
+    let b = checkout(&repo_path, &rev, &dir.path().join("b"));
+    let diff = diff_dirs(DIFF_FLAGS_NONE, &a, &b, None).unwrap();
+    assert!(diff.is_empty());
+    diff.print(&a, &b);
+
+    // A commit compares equal to its checkout, except for the xattrs
+    // bare-user repositories keep the ownership in
+    let repo = Repo::new(&gio::File::new_for_path(&repo_path));
+    repo.open(None).unwrap();
+    let (root, _) = repo.read_commit(&rev, None).unwrap();
+    assert!(diff_dirs(DIFF_FLAGS_IGNORE_XATTRS, &root, &a, None).unwrap().is_empty());
+}
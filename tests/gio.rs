@@ -0,0 +1,50 @@
+extern crate ostree;
+
+use std::io::{self, Cursor, Read};
+use std::sync::{Arc, Mutex};
+
+use ostree::gio;
+
+struct CountingReader {
+    inner: Cursor<Vec<u8>>,
+    reads: Arc<Mutex<usize>>,
+}
+
+impl Read for CountingReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        *self.reads.lock().unwrap() += 1;
+        self.inner.read(buf)
+    }
+}
+
+struct FailingReader;
+
+impl Read for FailingReader {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::from_raw_os_error(5))
+    }
+}
+
+#[test]
+fn from_read_pulls_on_demand() {
+    let reads = Arc::new(Mutex::new(0));
+    let reader = CountingReader { inner: Cursor::new(b"hello world".to_vec()), reads: reads.clone() };
+    let mut stream = gio::InputStream::from_read(reader);
+    assert_eq!(*reads.lock().unwrap(), 0);
+
+    let mut buf = [0; 5];
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"hello");
+    assert_eq!(*reads.lock().unwrap(), 1);
+
+    let mut rest = Vec::new();
+    stream.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b" world");
+}
+
+#[test]
+fn from_read_reports_errors() {
+    let mut stream = gio::InputStream::from_read(FailingReader);
+    let mut buf = [0; 4];
+    assert!(stream.read(&mut buf).is_err());
+}
@@ -0,0 +1,149 @@
+extern crate ostree;
+
+mod common;
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+use common::TempDir;
+use ostree::{gio, Repo};
+
+/// Creates a signing key in the GnuPG home `homedir`, returning its
+/// fingerprint.
+fn gen_key(homedir: &Path) -> String {
+    fs::create_dir_all(homedir).unwrap();
+    fs::set_permissions(homedir, fs::Permissions::from_mode(0o700)).unwrap();
+    let homedir = homedir.to_str().unwrap();
+    common::run("gpg", &["--homedir", homedir, "--batch", "--passphrase", "", "--quick-gen-key",
+                         "Test Key <test@example.com>", "rsa2048", "sign", "never"]);
+    let keys = common::run("gpg", &["--homedir", homedir, "--batch", "--list-secret-keys", "--with-colons"]);
+    keys.lines()
+        .find(|line| line.starts_with("fpr:"))
+        .and_then(|line| line.split(':').nth(9))
+        .expect("no fingerprint")
+        .to_owned()
+}
+
+#[test]
+fn sign_and_verify_commit() {
+    let dir = TempDir::new();
+    let homedir = dir.path().join("gnupg");
+    let fingerprint = gen_key(&homedir);
+    let keyring = dir.path().join("pubring.gpg");
+    common::run("gpg", &["--homedir", homedir.to_str().unwrap(), "--batch", "--output", keyring.to_str().unwrap(),
+                         "--export", &fingerprint]);
+
+    let repo_path = dir.path().join("repo");
+    common::init_repo(&repo_path);
+    let tree = dir.path().join("tree");
+    common::write_tree(&tree, &[("file", b"content")]);
+    let commit = common::commit(&repo_path, "test", &tree);
+
+    let repo = Repo::new(&gio::File::new_for_path(&repo_path));
+    repo.open(None).unwrap();
+    let keyring = gio::File::new_for_path(&keyring);
+    // Nothing to verify yet
+    assert!(repo.verify_commit_ext(&commit, None::<&gio::File>, Some(&keyring), None).is_err());
+
+    repo.sign_commit(&commit, &fingerprint, Some(homedir.as_path()), None).unwrap();
+
+    let result = repo.verify_commit_ext(&commit, None::<&gio::File>, Some(&keyring), None).unwrap();
+    assert_eq!(result.count_all(), 1);
+    assert_eq!(result.count_valid(), 1);
+    let signature = result.signature(0).unwrap();
+    assert!(signature.valid);
+    assert_eq!(signature.fingerprint, fingerprint);
+    assert_eq!(signature.user_email, "test@example.com");
+
+    // Without the public key the signature is there but can't be checked
+    let empty = TempDir::new();
+    let result = repo.verify_commit_ext(&commit, Some(&empty.file()), None::<&gio::File>, None).unwrap();
+    assert_eq!(result.count_all(), 1);
+    assert_eq!(result.count_valid(), 0);
+    assert!(result.signature(0).unwrap().key_missing);
+}
+
+#[test]
+fn sign_summary() {
+    let dir = TempDir::new();
+    let homedir = dir.path().join("gnupg");
+    let fingerprint = gen_key(&homedir);
+
+    let repo_path = dir.path().join("repo");
+    common::init_repo(&repo_path);
+    let tree = dir.path().join("tree");
+    common::write_tree(&tree, &[("file", b"content")]);
+    common::commit(&repo_path, "test", &tree);
+    common::run("ostree", &["summary", "--update", &format!("--repo={}", repo_path.display())]);
+
+    let repo = Repo::new(&gio::File::new_for_path(&repo_path));
+    repo.open(None).unwrap();
+    repo.add_gpg_signature_summary(&[&fingerprint], Some(homedir.as_path()), None).unwrap();
+
+    let signatures = fs::read(repo_path.join("summary.sig")).unwrap();
+    assert!(!signatures.is_empty());
+}
+
+#[test]
+fn iterate_signatures() {
+    let dir = TempDir::new();
+    let homedirs = [dir.path().join("gnupg1"), dir.path().join("gnupg2")];
+    let fingerprints: Vec<String> = homedirs.iter().map(|homedir| gen_key(homedir)).collect();
+    let keyring = dir.path().join("pubring.gpg");
+    // A keyring is just concatenated binary key exports
+    let mut keys = Vec::new();
+    for (homedir, fingerprint) in homedirs.iter().zip(&fingerprints) {
+        let exported = homedir.with_extension("gpg");
+        common::run("gpg", &["--homedir", homedir.to_str().unwrap(), "--batch", "--output", exported.to_str().unwrap(),
+                             "--export", fingerprint]);
+        keys.extend(fs::read(&exported).unwrap());
+    }
+    fs::write(&keyring, keys).unwrap();
+
+    let repo_path = dir.path().join("repo");
+    common::init_repo(&repo_path);
+    let tree = dir.path().join("tree");
+    common::write_tree(&tree, &[("file", b"content")]);
+    let commit = common::commit(&repo_path, "test", &tree);
+
+    let repo = Repo::new(&gio::File::new_for_path(&repo_path));
+    repo.open(None).unwrap();
+    for (homedir, fingerprint) in homedirs.iter().zip(&fingerprints) {
+        repo.sign_commit(&commit, fingerprint, Some(homedir.as_path()), None).unwrap();
+    }
+
+    let keyring = gio::File::new_for_path(&keyring);
+    let result = repo.verify_commit_ext(&commit, None::<&gio::File>, Some(&keyring), None).unwrap();
+    let mut signatures = result.signatures();
+    assert_eq!(signatures.size_hint(), (2, Some(2)));
+    let first = signatures.next().unwrap();
+    assert_eq!(signatures.size_hint(), (1, Some(1)));
+    let second = signatures.next().unwrap();
+    assert!(signatures.next().is_none());
+    assert!(result.signature(2).is_none());
+
+    assert_eq!(first.fingerprint, fingerprints[0]);
+    assert_eq!(second.fingerprint, fingerprints[1]);
+    for signature in &[first, second] {
+        assert!(signature.valid);
+        assert!(!signature.sig_expired && !signature.key_expired && !signature.key_revoked && !signature.key_missing);
+        assert!(signature.timestamp > 0);
+        assert_eq!(signature.exp_timestamp, 0);
+        assert_eq!(signature.pubkey_algo_name, "RSA");
+        assert!(!signature.hash_algo_name.is_empty());
+        assert_eq!(signature.user_name, "Test Key");
+        assert_eq!(signature.user_email, "test@example.com");
+    }
+    assert_eq!(result.signatures().collect::<Vec<_>>(), vec![result.signature(0).unwrap(), result.signature(1).unwrap()]);
+
+    // Without the public keys, OSTree fills in placeholders
+    let empty = TempDir::new();
+    let result = repo.verify_commit_ext(&commit, Some(&empty.file()), None::<&gio::File>, None).unwrap();
+    for signature in result.signatures() {
+        assert!(signature.key_missing);
+        assert!(!signature.valid);
+        assert_eq!(signature.user_name, "[unknown name]");
+        assert_eq!(signature.user_email, "[unknown email]");
+    }
+}
@@ -0,0 +1,96 @@
+extern crate glib;
+extern crate ostree;
+
+mod common;
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use glib::object::Downcast;
+use ostree::{gio, ObjectName, ObjectType, Repo, RepoFile};
+use ostree::gio::FileExt;
+
+use common::TempDir;
+
+// OSTREE_REPO_TEST_ERROR is read when a repository is created, so the tests
+// here must not overlap.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn open_repo(path: &Path) -> Repo {
+    let repo = Repo::new(&gio::File::new_for_path(path));
+    repo.open(None).unwrap();
+    repo
+}
+
+/// Commits a one-file tree, returning the source repository and the names
+/// of all objects of the commit.
+fn source_repo(dir: &TempDir) -> (Repo, Vec<ObjectName>) {
+    let repo_path = dir.path().join("source");
+    common::init_repo(&repo_path);
+    let tree = dir.path().join("tree");
+    common::write_tree(&tree, &[("file", b"content")]);
+    let commit = common::commit(&repo_path, "test", &tree);
+
+    let repo = open_repo(&repo_path);
+    let (root, _) = repo.read_commit(&commit, None).unwrap();
+    let root: RepoFile = root.downcast().unwrap();
+    root.ensure_resolved().unwrap();
+    let file: RepoFile = root.get_child("file").unwrap().downcast().unwrap();
+    file.ensure_resolved().unwrap();
+    let objects = vec![
+        ObjectName::new(commit, ObjectType::Commit),
+        ObjectName::new(root.tree_get_contents_checksum().unwrap(), ObjectType::DirTree),
+        ObjectName::new(root.tree_get_metadata_checksum().unwrap(), ObjectType::DirMeta),
+        ObjectName::new(file.get_checksum().unwrap(), ObjectType::File),
+    ];
+    (repo, objects)
+}
+
+fn dest_repo_path(dir: &TempDir) -> PathBuf {
+    let repo_path = dir.path().join("dest");
+    common::init_repo(&repo_path);
+    repo_path
+}
+
+#[test]
+fn missing_objects_are_reported() {
+    let _serial = SERIAL.lock().unwrap();
+    let dir = TempDir::new();
+    let (source, objects) = source_repo(&dir);
+    let dest = open_repo(&dest_repo_path(&dir));
+    let missing = ObjectName::new("1".repeat(64), ObjectType::File);
+
+    let mut all = objects.clone();
+    all.insert(2, missing.clone());
+    let failures = dest.import_objects_from(&source, all, false, None).unwrap();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].0, missing);
+
+    for object in &objects {
+        assert!(dest.has_object(object.object_type, &object.checksum, None).unwrap(), "{} not imported", object);
+    }
+    assert!(!dest.has_object(missing.object_type, &missing.checksum, None).unwrap());
+}
+
+#[test]
+fn failed_commit_aborts_the_transaction() {
+    let _serial = SERIAL.lock().unwrap();
+    let dir = TempDir::new();
+    let (source, objects) = source_repo(&dir);
+    let dest_path = dest_repo_path(&dir);
+    env::set_var("OSTREE_REPO_TEST_ERROR", "pre-commit");
+    let dest = Repo::new(&gio::File::new_for_path(&dest_path));
+    env::remove_var("OSTREE_REPO_TEST_ERROR");
+    dest.open(None).unwrap();
+
+    assert!(dest.import_objects_from(&source, objects.clone(), false, None).is_err());
+    // has_object() also looks at the staging directory of an open transaction
+    for object in &objects {
+        assert!(!dest.has_object(object.object_type, &object.checksum, None).unwrap(), "{} was committed", object);
+    }
+
+    // The repository is still usable
+    let dest = open_repo(&dest_path);
+    assert_eq!(dest.import_objects_from(&source, objects, false, None).unwrap().len(), 0);
+}
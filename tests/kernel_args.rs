@@ -0,0 +1,104 @@
+extern crate ostree;
+
+use ostree::KernelArgs;
+
+#[test]
+fn parse() {
+    let args = KernelArgs::parse("root=/dev/sda1 quiet rd.luks.options=\"discard timeout=10\" console=tty0");
+    assert_eq!(args.as_slice(), ["root=/dev/sda1", "quiet", "rd.luks.options=\"discard timeout=10\"", "console=tty0"]);
+    let pairs: Vec<_> = args.iter().collect();
+    assert_eq!(pairs, [
+        ("root", Some("/dev/sda1")),
+        ("quiet", None),
+        ("rd.luks.options", Some("\"discard timeout=10\"")),
+        ("console", Some("tty0")),
+    ]);
+    assert!(KernelArgs::parse("").is_empty());
+    assert!(KernelArgs::parse(" \t ").is_empty());
+}
+
+#[test]
+fn display_round_trips_single_spaces() {
+    let line = "root=/dev/sda1 quiet rd.luks.options=\"discard timeout=10\"";
+    assert_eq!(KernelArgs::parse(line).to_string(), line);
+}
+
+#[test]
+fn display_keeps_whitespace() {
+    let line = "  root=/dev/sda1\t\tquiet \n rd.luks.options=\"a  b\"  ";
+    let args = KernelArgs::parse(line);
+    assert_eq!(args.len(), 3);
+    assert_eq!(args.to_string(), line);
+    assert_eq!(KernelArgs::parse(" \t ").to_string(), " \t ");
+}
+
+#[test]
+fn edits_keep_surrounding_whitespace() {
+    let mut args = KernelArgs::parse("  console=tty0\tquiet  console=ttyS0   root=/dev/sda1\n");
+    args.replace("console=ttyS1");
+    assert_eq!(args.to_string(), "  console=ttyS1\tquiet   root=/dev/sda1\n");
+    args.append("rhgb");
+    assert_eq!(args.to_string(), "  console=ttyS1\tquiet   root=/dev/sda1 rhgb\n");
+    assert!(args.delete("console"));
+    assert_eq!(args.to_string(), "  quiet   root=/dev/sda1 rhgb\n");
+    assert!(args.delete("root"));
+    assert_eq!(args.to_string(), "  quiet rhgb\n");
+    assert!(args.delete("quiet") && args.delete("rhgb"));
+    assert_eq!(args.to_string(), "  \n");
+    args.append("quiet");
+    assert_eq!(args.to_string(), "quiet  \n");
+}
+
+#[test]
+fn from_vec_uses_single_spaces() {
+    let args = KernelArgs::from(vec!["root=/dev/sda1".to_owned(), "quiet".to_owned()]);
+    assert_eq!(args.to_string(), "root=/dev/sda1 quiet");
+    assert_eq!(args, KernelArgs::parse("root=/dev/sda1 quiet"));
+}
+
+#[test]
+fn duplicates() {
+    let mut args = KernelArgs::parse("console=tty0 quiet console=ttyS0,115200");
+    assert_eq!(args.values("console").collect::<Vec<_>>(), [Some("tty0"), Some("ttyS0,115200")]);
+    args.append("quiet");
+    assert_eq!(args.to_string(), "console=tty0 quiet console=ttyS0,115200 quiet");
+    assert_eq!(args.len(), 4);
+}
+
+#[test]
+fn contains() {
+    let args = KernelArgs::parse("root=/dev/sda1 quiet console=tty0 console=ttyS0");
+    assert!(args.contains("root"));
+    assert!(args.contains("root=/dev/sda1"));
+    assert!(!args.contains("root=/dev/sda2"));
+    assert!(args.contains("quiet"));
+    assert!(!args.contains("quiet="));
+    assert!(args.contains("console=ttyS0"));
+    assert!(!args.contains("rhgb"));
+    // Keys are matched whole
+    assert!(!args.contains("roo"));
+}
+
+#[test]
+fn replace() {
+    let mut args = KernelArgs::parse("console=tty0 quiet console=ttyS0 root=/dev/sda1");
+    args.replace("console=ttyS1");
+    assert_eq!(args.to_string(), "console=ttyS1 quiet root=/dev/sda1");
+    args.replace("rhgb");
+    assert_eq!(args.to_string(), "console=ttyS1 quiet root=/dev/sda1 rhgb");
+    args.replace("root=/dev/sda2");
+    assert_eq!(args.to_string(), "console=ttyS1 quiet root=/dev/sda2 rhgb");
+}
+
+#[test]
+fn delete() {
+    let mut args = KernelArgs::parse("console=tty0 quiet console=ttyS0 root=/dev/sda1");
+    assert!(args.delete("console=ttyS0"));
+    assert_eq!(args.to_string(), "console=tty0 quiet root=/dev/sda1");
+    assert!(!args.delete("console=ttyS0"));
+    assert!(args.delete("console"));
+    assert_eq!(args.to_string(), "quiet root=/dev/sda1");
+    assert!(args.delete("quiet"));
+    assert!(!args.delete("rhgb"));
+    assert_eq!(args.to_string(), "root=/dev/sda1");
+}
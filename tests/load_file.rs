@@ -0,0 +1,65 @@
+extern crate glib;
+extern crate ostree;
+
+mod common;
+
+use std::fs;
+use std::io::Read;
+use std::os::unix::fs::{symlink, PermissionsExt};
+
+use glib::object::Downcast;
+use ostree::{gio, ObjectType, Repo, RepoFile};
+use ostree::gio::FileExt;
+
+use common::TempDir;
+
+const S_IFMT: u32 = 0o170000;
+const S_IFREG: u32 = 0o100000;
+const S_IFLNK: u32 = 0o120000;
+
+fn child_checksum(root: &RepoFile, name: &str) -> String {
+    let child: RepoFile = root.get_child(name).unwrap().downcast().unwrap();
+    child.ensure_resolved().unwrap();
+    child.get_checksum().unwrap()
+}
+
+#[test]
+fn load_regular_file_and_symlink() {
+    let dir = TempDir::new();
+    let tree = dir.path().join("tree");
+    common::write_tree(&tree, &[("script", b"#!/bin/sh\necho hello\n")]);
+    fs::set_permissions(tree.join("script"), fs::Permissions::from_mode(0o755)).unwrap();
+    symlink("script", tree.join("link")).unwrap();
+    let repo_path = dir.path().join("repo");
+    common::init_repo(&repo_path);
+    let commit = common::commit(&repo_path, "test", &tree);
+
+    let repo = Repo::new(&gio::File::new_for_path(&repo_path));
+    repo.open(None).unwrap();
+    let (root, _) = repo.read_commit(&commit, None).unwrap();
+    let root: RepoFile = root.downcast().unwrap();
+
+    let script = child_checksum(&root, "script");
+    let object = repo.load_file(&script, None).unwrap();
+    assert_eq!(object.mode() & S_IFMT, S_IFREG);
+    assert_eq!(object.mode() & 0o7777, 0o755);
+    assert_eq!(object.size(), 22);
+    assert_eq!(object.symlink_target(), None);
+    let mut contents = Vec::new();
+    object.input.unwrap().read_to_end(&mut contents).unwrap();
+    assert_eq!(contents, b"#!/bin/sh\necho hello\n");
+
+    let link = child_checksum(&root, "link");
+    let object = repo.load_file(&link, None).unwrap();
+    assert_eq!(object.mode() & S_IFMT, S_IFLNK);
+    assert_eq!(object.symlink_target().as_ref().map(|s| &s[..]), Some("script"));
+    assert!(object.input.is_none());
+
+    // The object stream is the file in the content stream format: a header
+    // followed by the contents
+    let (mut stream, length) = repo.load_object_stream(ObjectType::File, &script, None).unwrap();
+    let mut object = Vec::new();
+    stream.read_to_end(&mut object).unwrap();
+    assert_eq!(object.len() as u64, length);
+    assert!(object.ends_with(b"#!/bin/sh\necho hello\n"));
+}
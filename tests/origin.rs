@@ -0,0 +1,93 @@
+extern crate ostree;
+
+mod common;
+
+use std::fs;
+
+use common::TempDir;
+use ostree::Origin;
+
+// As written by rpm-ostree, with the groups in an unusual order, an unknown
+// key in [origin] and an empty group
+const ORIGIN: &str = "\
+[rpmostree]
+regenerate-initramfs=true
+initramfs-args=--add;dracut-module;
+
+[origin]
+custom-url=https://example.com/repo
+refspec=fedora:fedora/28/x86_64/atomic-host
+unconfigured-state=Managed by\\nthe admin
+
+[packages]
+requested=vim;tmux;
+
+[empty]
+";
+
+#[test]
+fn round_trip() {
+    let origin = Origin::parse(ORIGIN).unwrap();
+    assert_eq!(origin.to_data().unwrap(), ORIGIN);
+    assert_eq!(Origin::from_key_file(&origin.to_key_file()).unwrap(), origin);
+
+    assert_eq!(origin.groups(), ["rpmostree", "origin", "packages", "empty"]);
+    assert_eq!(origin.keys("origin"), ["custom-url", "refspec", "unconfigured-state"]);
+    assert!(origin.keys("empty").is_empty());
+    assert_eq!(origin.refspec().unwrap(), "fedora:fedora/28/x86_64/atomic-host");
+    assert_eq!(origin.override_commit(), None);
+    assert_eq!(origin.unconfigured_state().unwrap(), "Managed by\nthe admin");
+    assert_eq!(origin.get("packages", "requested").unwrap(), "vim;tmux;");
+    assert_eq!(origin.get("packages", "missing"), None);
+}
+
+#[test]
+fn edits_keep_order() {
+    let mut origin = Origin::parse(ORIGIN).unwrap();
+    origin.set_refspec("fedora:fedora/29/x86_64/atomic-host");
+    origin.set_override_commit(Some("abc123"));
+    origin.set_unconfigured_state(None);
+    origin.set("packages", "requested", " leading space");
+    origin.set("new", "key", "value");
+    assert!(origin.remove("rpmostree", "initramfs-args"));
+    assert!(!origin.remove("rpmostree", "initramfs-args"));
+
+    assert_eq!(origin.to_data().unwrap(), "\
+[rpmostree]
+regenerate-initramfs=true
+
+[origin]
+custom-url=https://example.com/repo
+refspec=fedora:fedora/29/x86_64/atomic-host
+override-commit=abc123
+
+[packages]
+requested=\\sleading space
+
+[empty]
+
+[new]
+key=value
+");
+    assert_eq!(origin.get("packages", "requested").unwrap(), " leading space");
+}
+
+#[test]
+fn load_and_save() {
+    let dir = TempDir::new();
+    let path = dir.path().join("deploy.origin");
+    // Comments and whitespace around `=` are the only things not kept
+    let commented = ORIGIN.replace("[origin]\n", "# Written by hand\n[origin]\n")
+        .replace("custom-url=", "custom-url = ");
+    fs::write(&path, commented).unwrap();
+
+    let origin = Origin::load(&path).unwrap();
+    origin.save(&path).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), ORIGIN);
+}
+
+#[test]
+fn new() {
+    let origin = Origin::new("remote:ref");
+    assert_eq!(origin.to_data().unwrap(), "[origin]\nrefspec=remote:ref\n");
+}
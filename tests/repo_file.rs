@@ -0,0 +1,53 @@
+extern crate glib;
+extern crate ostree;
+
+mod common;
+
+use glib::object::Downcast;
+use ostree::{gio, Repo, RepoFile, TreeChild};
+use ostree::gio::FileExt;
+
+use common::TempDir;
+
+#[test]
+fn tree_find_child_gives_query_indices() {
+    let dir = TempDir::new();
+    let repo_path = dir.path().join("repo");
+    common::init_repo(&repo_path);
+    let tree = dir.path().join("tree");
+    common::write_tree(&tree, &[
+        ("a", b"a"),
+        ("b", b"b"),
+        ("dir1/c", b"c"),
+        ("dir2/d", b"d"),
+    ]);
+    common::commit(&repo_path, "test", &tree);
+
+    let repo = Repo::new(&gio::File::new_for_path(&repo_path));
+    repo.open(None).unwrap();
+    let (root, _) = repo.read_commit("test", None).unwrap();
+    let root: RepoFile = root.downcast().unwrap();
+
+    assert_eq!(root.tree_find_child("b").unwrap(), Some(TreeChild::File(1)));
+    assert_eq!(root.tree_find_child("missing").unwrap(), None);
+
+    let child = root.tree_find_child("dir2").unwrap().unwrap();
+    assert!(child.is_dir());
+    assert_eq!(child.index(), 3);
+    let info = root.tree_query_child(child.index(), "standard::name,standard::type", gio::FILE_QUERY_INFO_NONE, None)
+        .unwrap()
+        .unwrap();
+    assert_eq!(info.get_name().unwrap().to_str(), Some("dir2"));
+    assert_eq!(info.get_file_type(), gio::FileType::Directory);
+
+    for name in &["a", "b", "dir1", "dir2"] {
+        let child = root.tree_find_child(name).unwrap().unwrap();
+        let info = root.tree_query_child(child.index(), "standard::name", gio::FILE_QUERY_INFO_NONE, None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.get_name().unwrap().to_str(), Some(*name));
+    }
+
+    let dir1: RepoFile = root.get_child("dir1").unwrap().downcast().unwrap();
+    assert_eq!(dir1.tree_find_child("c").unwrap(), Some(TreeChild::File(0)));
+}
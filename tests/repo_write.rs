@@ -0,0 +1,126 @@
+extern crate glib;
+extern crate ostree;
+
+mod common;
+
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+use std::task::Poll;
+
+use glib::object::Downcast;
+use ostree::{gio, ObjectType, Repo, RepoFile};
+use ostree::gio::FileExt;
+
+use common::TempDir;
+
+fn open_repo(path: &Path) -> Repo {
+    let repo = Repo::new(&gio::File::new_for_path(path));
+    repo.open(None).unwrap();
+    repo
+}
+
+/// Commits a tree with one file, returning the repository and the root of
+/// the commit.
+fn source_repo(dir: &TempDir) -> (Repo, RepoFile) {
+    let repo_path = dir.path().join("source");
+    common::init_repo(&repo_path);
+    let tree = dir.path().join("tree");
+    common::write_tree(&tree, &[("file", b"content")]);
+    common::commit(&repo_path, "test", &tree);
+
+    let repo = open_repo(&repo_path);
+    let (root, _) = repo.read_commit("test", None).unwrap();
+    let root: RepoFile = root.downcast().unwrap();
+    root.ensure_resolved().unwrap();
+    (repo, root)
+}
+
+fn dest_repo(dir: &TempDir) -> Repo {
+    let repo_path = dir.path().join("dest");
+    common::init_repo(&repo_path);
+    open_repo(&repo_path)
+}
+
+#[test]
+fn write_futures_in_flight_together() {
+    let dir = TempDir::new();
+    let (source, root) = source_repo(&dir);
+    let file: RepoFile = root.get_child("file").unwrap().downcast().unwrap();
+    file.ensure_resolved().unwrap();
+    let file_checksum = file.get_checksum().unwrap();
+    let (stream, length) = source.load_object_stream(ObjectType::File, &file_checksum, None).unwrap();
+    let tree_checksum = root.tree_get_contents_checksum().unwrap();
+    let tree = root.tree_get_contents().unwrap();
+
+    let dest = dest_repo(&dir);
+    dest.prepare_transaction(None).unwrap();
+    let mut content = Box::pin(dest.write_content_future(&file_checksum[..], &stream, length));
+    let mut dirtree = Box::pin(dest.write_metadata_future(ObjectType::DirTree, &tree_checksum[..], &tree));
+    let mut content_result = None;
+    let mut dirtree_result = None;
+    loop {
+        if content_result.is_none() {
+            if let Poll::Ready(result) = common::poll_once(content.as_mut()) {
+                content_result = Some(result);
+            }
+        }
+        if dirtree_result.is_none() {
+            if let Poll::Ready(result) = common::poll_once(dirtree.as_mut()) {
+                dirtree_result = Some(result);
+            }
+        }
+        if content_result.is_some() && dirtree_result.is_some() {
+            break;
+        }
+        common::iterate_main_context();
+    }
+    dest.commit_transaction(None).unwrap();
+
+    assert_eq!(content_result.unwrap().unwrap().to_string(), file_checksum);
+    assert_eq!(dirtree_result.unwrap().unwrap().to_string(), tree_checksum);
+    assert!(dest.has_object(ObjectType::File, &file_checksum, None).unwrap());
+    assert!(dest.has_object(ObjectType::DirTree, &tree_checksum, None).unwrap());
+}
+
+#[test]
+fn write_metadata_async_runs_callback_on_main_context() {
+    let dir = TempDir::new();
+    let (_, root) = source_repo(&dir);
+    let meta_checksum = root.tree_get_metadata_checksum().unwrap();
+    let meta = root.tree_get_metadata().unwrap();
+
+    let dest = dest_repo(&dir);
+    dest.prepare_transaction(None).unwrap();
+    let result = Rc::new(RefCell::new(None));
+    let result_clone = result.clone();
+    dest.write_metadata_async(ObjectType::DirMeta, None, &meta, None, move |res| {
+        *result_clone.borrow_mut() = Some(res);
+    });
+    // Only invoked from the main context
+    assert!(result.borrow().is_none());
+    while result.borrow().is_none() {
+        common::iterate_main_context();
+    }
+    dest.commit_transaction(None).unwrap();
+
+    let checksum = result.borrow_mut().take().unwrap().unwrap();
+    assert_eq!(checksum.to_string(), meta_checksum);
+    assert!(dest.has_object(ObjectType::DirMeta, &meta_checksum, None).unwrap());
+}
+
+#[test]
+fn write_content_future_checks_the_expected_checksum() {
+    let dir = TempDir::new();
+    let (source, root) = source_repo(&dir);
+    let file: RepoFile = root.get_child("file").unwrap().downcast().unwrap();
+    file.ensure_resolved().unwrap();
+    let file_checksum = file.get_checksum().unwrap();
+    let (stream, length) = source.load_object_stream(ObjectType::File, &file_checksum, None).unwrap();
+
+    let dest = dest_repo(&dir);
+    dest.prepare_transaction(None).unwrap();
+    let wrong = "0".repeat(64);
+    assert!(common::block_on(dest.write_content_future(&wrong[..], &stream, length)).is_err());
+    dest.abort_transaction(None).unwrap();
+}
@@ -0,0 +1,41 @@
+extern crate ostree;
+
+mod common;
+
+use std::fs;
+
+use common::{new_sysroot, TempDir, OSNAME};
+use ostree::gio::FileExt;
+use ostree::{SePolicy, SEPOLICY_RESTORECON_FLAGS_NONE};
+
+/// The test tree has no `/etc/selinux/config`, so there is no policy and
+/// labeling is a no-op whether or not the host uses SELinux.
+#[test]
+fn deployment_without_policy() {
+    let dir = TempDir::new();
+    let sysroot = new_sysroot(&dir);
+    sysroot.init_osname(OSNAME, None).unwrap();
+    let rev = common::commit_os_tree(&sysroot, "1");
+    let deployment = common::deploy_commit(&sysroot, &rev, &[]);
+    let root = sysroot.get_deployment_directory(&deployment).unwrap();
+
+    let policy = SePolicy::new(&root, None).unwrap();
+    assert_eq!(policy.get_name(), None);
+    assert_eq!(policy.get_path().unwrap().get_path(), root.get_path());
+    assert_eq!(policy.get_label("/usr/bin/true", 0o100755, None).unwrap(), None);
+    let target = root.get_child("usr/bin/true").unwrap();
+    assert_eq!(policy.restorecon("/usr/bin/true", None, &target, SEPOLICY_RESTORECON_FLAGS_NONE, None).unwrap(), None);
+}
+
+#[test]
+fn fscreatecon_cleanup() {
+    let dir = TempDir::new();
+    let policy = SePolicy::new(&dir.file(), None).unwrap();
+
+    policy.setfscreatecon("/new-file", 0o100644).unwrap();
+    fs::write(dir.path().join("new-file"), b"").unwrap();
+    SePolicy::fscreatecon_cleanup();
+    // Resetting again is harmless
+    SePolicy::fscreatecon_cleanup();
+    fs::write(dir.path().join("other-file"), b"").unwrap();
+}
@@ -0,0 +1,73 @@
+extern crate ostree;
+
+mod common;
+
+use std::fs;
+
+use ostree::{gio, Repo, StaticDeltaSuperblock};
+
+use common::TempDir;
+
+/// Returns the number after `prefix` on the line starting with it.
+fn field(show: &str, prefix: &str) -> u64 {
+    let line = show.lines().find(|line| line.starts_with(prefix)).unwrap_or_else(|| panic!("no {} in {}", prefix, show));
+    line[prefix.len()..].split_whitespace().next().unwrap().parse().unwrap()
+}
+
+#[test]
+fn load_generated_delta() {
+    let dir = TempDir::new();
+    let repo_path = dir.path().join("repo");
+    common::init_repo(&repo_path);
+    let tree = dir.path().join("tree");
+    common::write_tree(&tree, &[("a", b"first"), ("dir/b", b"second")]);
+    let from = common::commit(&repo_path, "test", &tree);
+    common::write_tree(&tree, &[("a", b"changed"), ("dir/c", b"third")]);
+    let to = common::commit(&repo_path, "test", &tree);
+
+    let repo_arg = format!("--repo={}", repo_path.display());
+    common::run("ostree", &["static-delta", "generate", &repo_arg, &format!("--from={}", from), &format!("--to={}", to)]);
+    let name = format!("{}-{}", from, to);
+    let show = common::run("ostree", &["static-delta", "show", &repo_arg, &name]);
+
+    let repo = Repo::new(&gio::File::new_for_path(&repo_path));
+    repo.open(None).unwrap();
+    let superblock = StaticDeltaSuperblock::load(&repo, &from[..], &to).unwrap();
+    assert_eq!(superblock.from.as_ref(), Some(&from));
+    assert_eq!(superblock.to, to);
+    assert_eq!(superblock.parts.len() as u64, field(&show, "Number of parts:"));
+    assert_eq!(superblock.fallback.len() as u64, field(&show, "Number of fallback entries:"));
+    let n_objects: u64 = show.lines()
+        .filter(|line| line.starts_with("PartMeta"))
+        .map(|line| field(line.split_whitespace().nth(1).unwrap(), "nobjects="))
+        .sum();
+    assert!(n_objects > 0);
+    assert_eq!(superblock.n_objects() as u64, n_objects);
+    assert_eq!(superblock.total_size(),
+               field(&show, "Total Part Size:") + field(&show, "Total Fallback Size:"));
+
+    let by_name = StaticDeltaSuperblock::load_by_name(&repo, &name).unwrap();
+    assert_eq!(by_name.parts, superblock.parts);
+}
+
+#[test]
+fn from_data_reads_a_standalone_delta() {
+    let dir = TempDir::new();
+    let repo_path = dir.path().join("repo");
+    common::init_repo(&repo_path);
+    let tree = dir.path().join("tree");
+    common::write_tree(&tree, &[("a", b"content")]);
+    let to = common::commit(&repo_path, "test", &tree);
+    let repo_arg = format!("--repo={}", repo_path.display());
+    let filename = dir.path().join("delta");
+    common::run("ostree", &["static-delta", "generate", &repo_arg, "--empty", &format!("--to={}", to),
+                            &format!("--filename={}", filename.display())]);
+
+    let data = fs::read(&filename).unwrap();
+    let superblock = StaticDeltaSuperblock::from_data(&data).unwrap();
+    assert_eq!(superblock.from, None);
+    assert_eq!(superblock.to, to);
+
+    // An empty target checksum is an error, not an empty string
+    assert!(StaticDeltaSuperblock::from_data(&[]).is_err());
+}
@@ -0,0 +1,133 @@
+extern crate ostree;
+
+mod common;
+
+use std::fs;
+
+use common::{new_sysroot, TempDir, OSNAME};
+use ostree::gio::FileExt;
+use ostree::{DeploymentUnlockedState, KernelArgs};
+
+#[test]
+fn empty_sysroot_has_no_deployments() {
+    let dir = TempDir::new();
+    let sysroot = new_sysroot(&dir);
+
+    assert!(sysroot.get_deployments().is_empty());
+    assert!(sysroot.get_booted_deployment().is_none());
+    assert_eq!(sysroot.get_bootversion(), 0);
+}
+
+#[test]
+fn deploy_tree() {
+    let dir = TempDir::new();
+    let sysroot = new_sysroot(&dir);
+    sysroot.init_osname(OSNAME, None).unwrap();
+    let rev = common::commit_os_tree(&sysroot, "1");
+
+    let deployment = common::deploy_commit(&sysroot, &rev, &["root=/dev/sda1", "quiet"]);
+    assert_eq!(deployment.get_osname().unwrap(), OSNAME);
+    assert_eq!(deployment.get_csum().unwrap(), rev);
+
+    assert_eq!(sysroot.get_bootversion(), 1);
+    let deployments = sysroot.get_deployments();
+    assert_eq!(deployments.len(), 1);
+    // Equal as deployments, though a different object after reloading
+    let written = &deployments[0];
+    assert_eq!(*written, deployment);
+    let options = written.get_bootconfig().unwrap().options().unwrap();
+    assert!(options.contains("root=/dev/sda1"));
+    assert!(options.contains("quiet"));
+    assert!(options.contains("ostree"));
+
+    let deployment_dir = sysroot.get_deployment_directory(written).unwrap().get_path().unwrap();
+    assert!(deployment_dir.join("usr/bin/true").exists());
+    assert!(deployment_dir.join("etc/os-release").exists());
+    let origin = sysroot.load_deployment_origin(written).unwrap().unwrap();
+    assert_eq!(origin.refspec().unwrap(), "testos/stable");
+    assert_eq!(fs::read_dir(dir.path().join("boot/loader/entries")).unwrap().count(), 1);
+}
+
+#[test]
+fn set_kernel_args() {
+    let dir = TempDir::new();
+    let sysroot = new_sysroot(&dir);
+    sysroot.init_osname(OSNAME, None).unwrap();
+    let rev = common::commit_os_tree(&sysroot, "1");
+    common::deploy_commit(&sysroot, &rev, &["root=/dev/sda1", "quiet"]);
+
+    let deployment = sysroot.get_deployments().remove(0);
+    sysroot.deployment_set_kargs(&deployment, vec!["root=/dev/sdb1".to_owned(), "console=ttyS0".to_owned()], None).unwrap();
+    sysroot.load(None).unwrap();
+    let options = sysroot.get_deployments()[0].get_bootconfig().unwrap().options().unwrap();
+    assert!(options.contains("root=/dev/sdb1"));
+    assert!(options.contains("console=ttyS0"));
+    assert!(!options.contains("quiet"));
+
+    let deployment = sysroot.get_deployments().remove(0);
+    let mut args = options.clone();
+    args.replace("console=tty0");
+    args.append("quiet");
+    sysroot.deployment_set_kernel_args(&deployment, &args, None).unwrap();
+    sysroot.load(None).unwrap();
+    let options: KernelArgs = sysroot.get_deployments()[0].get_bootconfig().unwrap().options().unwrap();
+    assert_eq!(options.values("console").collect::<Vec<_>>(), [Some("tty0")]);
+    assert!(options.contains("quiet"));
+}
+
+#[test]
+fn cleanup_removes_undeployed_trees() {
+    let dir = TempDir::new();
+    let sysroot = new_sysroot(&dir);
+    sysroot.init_osname(OSNAME, None).unwrap();
+    let rev = common::commit_os_tree(&sysroot, "1");
+
+    // Deployed, but never written to the boot configuration
+    let deployment = sysroot.deploy_tree(OSNAME, &rev, None, None, None, None).unwrap();
+    let deployment_dir = sysroot.get_deployment_directory(&deployment).unwrap().get_path().unwrap();
+    assert!(deployment_dir.exists());
+
+    // Everything but pruning the repository
+    sysroot.prepare_cleanup(None).unwrap();
+    assert!(!deployment_dir.exists());
+    sysroot.cleanup(None).unwrap();
+}
+
+#[test]
+fn cleanup_keeps_written_deployments() {
+    let dir = TempDir::new();
+    let sysroot = new_sysroot(&dir);
+    sysroot.init_osname(OSNAME, None).unwrap();
+    let rev = common::commit_os_tree(&sysroot, "1");
+    let deployment = common::deploy_commit(&sysroot, &rev, &[]);
+    let old_dir = sysroot.get_deployment_directory(&deployment).unwrap().get_path().unwrap();
+
+    let rev = common::commit_os_tree(&sysroot, "2");
+    let deployment = common::deploy_commit(&sysroot, &rev, &[]);
+    let new_dir = sysroot.get_deployment_directory(&deployment).unwrap().get_path().unwrap();
+
+    // simple_write_deployment() already cleaned up the replaced deployment
+    sysroot.cleanup(None).unwrap();
+    assert!(!old_dir.exists());
+    assert!(new_dir.exists());
+    assert_eq!(sysroot.get_deployments().len(), 1);
+}
+
+#[test]
+fn unlock_and_set_mutable() {
+    let dir = TempDir::new();
+    let sysroot = new_sysroot(&dir);
+    sysroot.init_osname(OSNAME, None).unwrap();
+    let rev = common::commit_os_tree(&sysroot, "1");
+    common::deploy_commit(&sysroot, &rev, &[]);
+
+    let deployment = sysroot.get_deployments().remove(0);
+    assert_eq!(deployment.get_unlocked(), DeploymentUnlockedState::None);
+    // Already in that state
+    assert!(sysroot.deployment_unlock(&deployment, DeploymentUnlockedState::None, None).is_err());
+
+    // Making it immutable again would need CAP_LINUX_IMMUTABLE
+    sysroot.deployment_set_mutable(&deployment, true, None).unwrap();
+    let deployment_dir = sysroot.get_deployment_directory(&deployment).unwrap().get_path().unwrap();
+    fs::write(deployment_dir.join("usr/new-file"), b"").unwrap();
+}
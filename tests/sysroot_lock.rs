@@ -0,0 +1,63 @@
+extern crate ostree;
+
+mod common;
+
+use common::{new_sysroot, TempDir};
+
+#[test]
+fn lock_drop_and_relock() {
+    let dir = TempDir::new();
+    let sysroot = new_sysroot(&dir);
+    let other = new_sysroot(&dir);
+
+    let lock = common::block_on(sysroot.lock_future()).unwrap();
+    // The guard derefs to the sysroot
+    assert_eq!(lock.get_bootversion(), 0);
+    assert!(!other.try_lock().unwrap());
+
+    drop(lock);
+    assert!(other.try_lock().unwrap());
+    other.unlock();
+
+    let lock = common::block_on(sysroot.lock_future()).unwrap();
+    assert!(!other.try_lock().unwrap());
+    drop(lock);
+}
+
+#[test]
+fn dropping_a_finished_future_unlocks() {
+    let dir = TempDir::new();
+    let sysroot = new_sysroot(&dir);
+    let other = new_sysroot(&dir);
+
+    let mut future = Box::pin(sysroot.lock_future());
+    assert!(common::poll_once(future.as_mut()).is_pending());
+    // Dispatches the completion, without polling the future again
+    common::iterate_main_context();
+    assert!(!other.try_lock().unwrap());
+
+    drop(future);
+    assert!(other.try_lock().unwrap());
+    other.unlock();
+}
+
+#[test]
+fn dropping_a_waiting_future_releases_the_late_lock() {
+    let dir = TempDir::new();
+    let sysroot = new_sysroot(&dir);
+    let holder = new_sysroot(&dir);
+    let other = new_sysroot(&dir);
+    holder.lock().unwrap();
+
+    let mut future = Box::pin(sysroot.lock_future());
+    assert!(common::poll_once(future.as_mut()).is_pending());
+    // Cancels the future, but the worker thread still waits for the lock
+    drop(future);
+    holder.unlock();
+
+    // Once it gets the lock, the completion releases it again
+    while !other.try_lock().unwrap() {
+        common::iterate_main_context();
+    }
+    other.unlock();
+}
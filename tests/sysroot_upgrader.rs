@@ -0,0 +1,62 @@
+extern crate ostree;
+
+mod common;
+
+use common::{new_sysroot, TempDir, OSNAME};
+use ostree::{AsyncProgress, SysrootUpgrader, REPO_PULL_FLAGS_NONE, SYSROOT_UPGRADER_FLAGS_IGNORE_UNCONFIGURED,
+             SYSROOT_UPGRADER_PULL_FLAGS_NONE};
+
+#[test]
+fn pull_and_deploy_a_local_ref() {
+    let dir = TempDir::new();
+    let sysroot = new_sysroot(&dir);
+    sysroot.init_osname(OSNAME, None).unwrap();
+    let first = common::commit_os_tree(&sysroot, "1");
+    common::deploy_commit(&sysroot, &first, &[]);
+
+    let upgrader = SysrootUpgrader::new_for_os(&sysroot, OSNAME, None).unwrap();
+    assert_eq!(upgrader.get_origin_description().unwrap(), "testos/stable");
+    let origin = sysroot.new_origin("testos/stable").unwrap();
+    assert_eq!(ostree::Origin::from_key_file(&upgrader.dup_origin().unwrap()).unwrap().refspec(), origin.refspec());
+
+    // Nothing new yet
+    assert!(!upgrader.pull(REPO_PULL_FLAGS_NONE, SYSROOT_UPGRADER_PULL_FLAGS_NONE, None, None).unwrap());
+
+    let second = common::commit_os_tree(&sysroot, "2");
+    let progress = AsyncProgress::new();
+    assert!(upgrader.pull(REPO_PULL_FLAGS_NONE, SYSROOT_UPGRADER_PULL_FLAGS_NONE, &progress, None).unwrap());
+    upgrader.deploy(None).unwrap();
+
+    sysroot.load(None).unwrap();
+    let deployments = sysroot.get_deployments();
+    // The new deployment first, the merge deployment kept as rollback
+    assert_eq!(deployments.len(), 2);
+    assert_eq!(deployments[0].get_csum().unwrap(), second);
+    assert_eq!(deployments[1].get_csum().unwrap(), first);
+
+    let repo = sysroot.get_repo(None).unwrap();
+    SysrootUpgrader::check_timestamps(&repo, &first, &second).unwrap();
+}
+
+#[test]
+fn set_origin() {
+    let dir = TempDir::new();
+    let sysroot = new_sysroot(&dir);
+    sysroot.init_osname(OSNAME, None).unwrap();
+    let rev = common::commit_os_tree(&sysroot, "1");
+    common::deploy_commit(&sysroot, &rev, &[]);
+
+    let upgrader = SysrootUpgrader::new_for_os(&sysroot, OSNAME, None).unwrap();
+    let mut origin = sysroot.new_origin("testos/next").unwrap();
+    upgrader.set_origin(Some(&origin.to_key_file()), None).unwrap();
+    assert_eq!(upgrader.get_origin_description().unwrap(), "testos/next");
+
+    // An unconfigured origin is refused unless ignored
+    origin.set_unconfigured_state(Some("Run the setup tool first"));
+    let deployment = sysroot.get_deployments().remove(0);
+    sysroot.save_deployment_origin(&deployment, &origin).unwrap();
+    sysroot.load(None).unwrap();
+    let err = SysrootUpgrader::new_for_os(&sysroot, OSNAME, None).err().unwrap();
+    assert!(err.to_string().contains("Run the setup tool first"));
+    SysrootUpgrader::new_for_os_with_flags(&sysroot, OSNAME, SYSROOT_UPGRADER_FLAGS_IGNORE_UNCONFIGURED, None).unwrap();
+}